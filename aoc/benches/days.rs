@@ -0,0 +1,74 @@
+//! Benchmarks parsing and both parts of every day separately.
+//!
+//! Each day runs on its sample unless `AOC_BENCH_INPUT` names another input.
+//! The variable is a path in which `{day}` is replaced by the day's crate
+//! name. Cargo runs benchmarks from the `aoc` directory, so relative paths
+//! start there:
+//!
+//! ```sh
+//! AOC_BENCH_INPUT="$PWD/{day}/input.txt" cargo bench -p aoc
+//! ```
+//!
+//! `AOC_THREADS` sets the threads of the parallel solutions as for
+//! `aoc run`, e.g. `AOC_THREADS=sequential` to measure them on one thread.
+//!
+//! `AOC_BENCH_SEED` instead runs every day on an input generated from the
+//! seed, as large as the real puzzle input:
+//!
+//! ```sh
+//! AOC_BENCH_SEED=1 cargo bench -p aoc
+//! ```
+//!
+//! Filter the benchmarks as usual, e.g. `cargo bench -p aoc -- 06_six/part_2`.
+
+#![allow(
+    unused_crate_dependencies,
+    reason = "the dependencies are used through the library target"
+)]
+
+use aoc::registry::{Day, DAYS};
+use aoc::runner;
+use aoc_common::{parallel, Part};
+use criterion::{criterion_group, criterion_main, Criterion};
+use std::env;
+use std::hint::black_box;
+use std::path::PathBuf;
+
+const INPUT_VARIABLE: &str = "AOC_BENCH_INPUT";
+const SEED_VARIABLE: &str = "AOC_BENCH_SEED";
+
+fn bench_input(day: &Day) -> String {
+    if let Ok(seed) = env::var(SEED_VARIABLE) {
+        let seed = seed.parse().expect("the benchmark seed is a number");
+        return day.generate(seed, None);
+    }
+
+    let path = env::var(INPUT_VARIABLE)
+        .ok()
+        .map(|template| PathBuf::from(template.replace("{day}", day.name)));
+
+    runner::read_input(day, path.as_deref()).expect("benchmark input can be read")
+}
+
+fn bench_days(criterion: &mut Criterion) {
+    parallel::configure_from_env().expect("AOC_THREADS is a valid parallelism");
+
+    for day in DAYS {
+        let input = bench_input(day);
+        let parsed = day.parse(&input).expect("benchmark input can be read");
+
+        let mut group = criterion.benchmark_group(format!("{:02}_{}", day.number, day.name));
+        group.bench_function("parse", |bencher| {
+            bencher.iter(|| day.parse(black_box(&input)).is_ok());
+        });
+        for part in Part::ALL {
+            group.bench_function(format!("part_{part}"), |bencher| {
+                bencher.iter(|| parsed.solve(black_box(part)));
+            });
+        }
+        group.finish();
+    }
+}
+
+criterion_group!(benches, bench_days);
+criterion_main!(benches);
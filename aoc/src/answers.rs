@@ -0,0 +1,256 @@
+//! Expected answers for the example inputs shipped with every day.
+//!
+//! Each day's directory contains an `answers.toml` with one table per
+//! example input, named after the input file without its `.txt` extension:
+//!
+//! ```toml
+//! [sample]
+//! part_one = 11
+//! part_two = 31
+//! ```
+//!
+//! A part may be left out if the example has no known answer for it.
+
+use crate::registry::Day;
+use aoc_common::{input, Part};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use toml::de::Error as TomlError;
+
+pub const ANSWERS_FILE: &str = "answers.toml";
+
+/// An answer as written in the answers file.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(untagged)]
+enum Answer {
+    Integer(i64),
+    Text(String),
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Integer(answer) => answer.fmt(formatter),
+            Self::Text(answer) => answer.fmt(formatter),
+        }
+    }
+}
+
+/// The known answers for one example input.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Expected {
+    part_one: Option<Answer>,
+    part_two: Option<Answer>,
+}
+
+impl Expected {
+    #[must_use]
+    pub fn get(&self, part: Part) -> Option<String> {
+        match part {
+            Part::One => self.part_one.as_ref(),
+            Part::Two => self.part_two.as_ref(),
+        }
+        .map(Answer::to_string)
+    }
+}
+
+/// All example inputs of a day and their expected answers.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(transparent)]
+pub struct Answers {
+    samples: BTreeMap<String, Expected>,
+}
+
+impl Answers {
+    /// Reads the answers file in `day`'s directory.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`AnswersError`] if the file cannot be read or parsed.
+    pub fn load(day: &Day) -> Result<Self, AnswersError> {
+        let path = day.directory().join(ANSWERS_FILE);
+        let contents = fs::read_to_string(&path).map_err(|source| AnswersError::Io {
+            path: path.clone(),
+            source,
+        })?;
+
+        toml::from_str(&contents).map_err(|source| AnswersError::Toml { path, source })
+    }
+
+    /// Names of the example inputs with their expected answers.
+    pub fn samples(&self) -> impl Iterator<Item = (&str, &Expected)> + '_ {
+        self.samples
+            .iter()
+            .map(|(name, expected)| (name.as_str(), expected))
+    }
+}
+
+/// Path of the example input called `name` in `day`'s directory.
+#[must_use]
+pub fn sample_path(day: &Day, name: &str) -> PathBuf {
+    day.directory().join(format!("{name}.txt"))
+}
+
+/// An answer that differs from the answers file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mismatch {
+    pub day: u8,
+    pub sample: String,
+    pub part: Part,
+    pub expected: String,
+    /// The computed answer, or why none could be computed.
+    pub actual: Result<String, String>,
+}
+
+impl fmt::Display for Mismatch {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            formatter,
+            "day {} {}, part {}:",
+            self.day, self.sample, self.part
+        )?;
+        writeln!(formatter, "  - expected: {}", self.expected)?;
+        match &self.actual {
+            Ok(actual) => write!(formatter, "  + actual:   {actual}"),
+            Err(error) => write!(formatter, "  + error:    {error}"),
+        }
+    }
+}
+
+/// Solves every example input of `day` and collects the answers that differ
+/// from the answers file.
+///
+/// # Errors
+///
+/// Returns an [`AnswersError`] if the answers file or an example input
+/// cannot be read.
+pub fn check(day: &Day) -> Result<Vec<Mismatch>, AnswersError> {
+    let mut mismatches = Vec::new();
+
+    for (sample, known) in Answers::load(day)?.samples() {
+        let path = sample_path(day, sample);
+        let input = fs::read_to_string(&path)
+            .map(|contents| input::normalize(&contents))
+            .map_err(|source| AnswersError::Io { path, source })?;
+        let parts = Part::ALL
+            .into_iter()
+            .filter_map(|part| known.get(part).map(|answer| (part, answer)))
+            .collect::<Vec<_>>();
+        let to_solve = parts.iter().map(|&(part, _)| part).collect::<Vec<_>>();
+
+        let actual = match day.solve(&input, &to_solve) {
+            Ok(solved) => solved
+                .parts
+                .into_iter()
+                .map(|result| {
+                    result
+                        .map(|result| result.answer)
+                        .map_err(|error| error.to_string())
+                })
+                .collect(),
+            Err(error) => vec![Err(error.to_string()); parts.len()],
+        };
+
+        mismatches.extend(
+            parts
+                .into_iter()
+                .zip(actual)
+                .filter(|((_, expected), actual)| actual.as_ref() != Ok(expected))
+                .map(|((part, expected), actual)| Mismatch {
+                    day: day.number,
+                    sample: sample.to_owned(),
+                    part,
+                    expected,
+                    actual,
+                }),
+        );
+    }
+
+    Ok(mismatches)
+}
+
+#[derive(Debug)]
+pub enum AnswersError {
+    Io { path: PathBuf, source: io::Error },
+    Toml { path: PathBuf, source: TomlError },
+}
+
+impl AnswersError {
+    fn path(&self) -> &Path {
+        match self {
+            Self::Io { path, .. } | Self::Toml { path, .. } => path,
+        }
+    }
+}
+
+impl fmt::Display for AnswersError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(formatter, "failed to read {}", self.path().display())
+    }
+}
+
+impl Error for AnswersError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Io { source, .. } => Some(source),
+            Self::Toml { source, .. } => Some(source),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::DAYS;
+    use std::fmt::Write;
+
+    #[test]
+    fn test_parse_answers() {
+        let answers = toml::from_str::<Answers>(
+            "[sample]\npart_one = 11\npart_two = \"none\"\n\n[small]\npart_two = 3\n",
+        )
+        .expect("valid answers file");
+        let samples = answers
+            .samples()
+            .map(|(name, expected)| (name, expected.get(Part::One), expected.get(Part::Two)))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            samples,
+            [
+                ("sample", Some("11".to_owned()), Some("none".to_owned())),
+                ("small", None, Some("3".to_owned())),
+            ]
+        );
+        assert!(toml::from_str::<Answers>("[sample]\npart_three = 1\n")
+            .ok()
+            .is_none());
+    }
+
+    #[test]
+    fn test_sample_answers() {
+        let mut report = String::new();
+
+        for day in DAYS {
+            match check(day) {
+                Ok(mismatches) => {
+                    for mismatch in mismatches {
+                        writeln!(report, "{mismatch}").expect("writing to a string");
+                    }
+                }
+                Err(error) => {
+                    let cause = error.source().map(ToString::to_string).unwrap_or_default();
+                    writeln!(report, "day {}: {error}: {cause}", day.number)
+                        .expect("writing to a string");
+                }
+            }
+        }
+
+        assert!(report.is_empty(), "answers changed:\n{report}");
+    }
+}
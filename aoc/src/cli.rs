@@ -0,0 +1,184 @@
+//! Command line interface of the `aoc` binary.
+
+use crate::output::Format;
+use aoc_common::parallel::{Parallelism, ParallelismError};
+use aoc_common::Part;
+use clap::{Args, Parser, Subcommand};
+use std::path::PathBuf;
+
+/// Advent of Code 2024 solutions.
+#[derive(Debug, Parser)]
+#[command(name = "aoc")]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Command,
+    /// Threads for the parallel parts of the solutions: a number, `auto` or
+    /// `sequential`. Defaults to `AOC_THREADS`, then one per CPU.
+    #[arg(long, global = true)]
+    pub threads: Option<Parallelism>,
+    /// Run everything on a single thread, the same as `--threads sequential`.
+    #[arg(long, global = true, conflicts_with = "threads")]
+    pub sequential: bool,
+}
+
+impl Cli {
+    /// The parallelism from the options, then from `AOC_THREADS`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ParallelismError::Invalid`] if `AOC_THREADS` is needed but
+    /// invalid.
+    pub fn parallelism(&self) -> Result<Parallelism, ParallelismError> {
+        if self.sequential {
+            Ok(Parallelism::Sequential)
+        } else {
+            self.threads.map_or_else(Parallelism::from_env, Ok)
+        }
+    }
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Run one day or all of them and print a summary.
+    Run(RunArgs),
+    /// Download the puzzle input of one day or all of them into the cache.
+    Fetch(FetchArgs),
+    /// Submit the answer to one part of a day.
+    Submit(SubmitArgs),
+    /// Create the crate of a new day and register it with the runner.
+    New(NewArgs),
+    /// Rebuild and rerun a day whenever its sources or input change.
+    Watch(WatchArgs),
+    /// Write a synthetic input for a day.
+    Generate(GenerateArgs),
+    /// Compare every implementation of a day's parts on its sample and on
+    /// generated inputs.
+    Check(CheckArgs),
+    /// Run every day and write an HTML page with the answers, timings and
+    /// renderings. The timings are also appended to `reports/runs.jsonl` in
+    /// the workspace, for the history shown next to them.
+    Report(ReportArgs),
+}
+
+#[derive(Debug, Args)]
+pub struct RunArgs {
+    /// Day to run, as a number or crate name.
+    #[arg(required_unless_present = "all")]
+    pub day: Option<String>,
+    /// Run every day.
+    #[arg(long, conflicts_with_all = ["day", "input"])]
+    pub all: bool,
+    /// Only run this part (1 or 2).
+    #[arg(long)]
+    pub part: Option<Part>,
+    /// Input file to use instead of the day's sample, or `-` for standard
+    /// input.
+    #[arg(long)]
+    pub input: Option<PathBuf>,
+    /// Run on an input generated from this seed instead of the sample.
+    #[arg(long, conflicts_with = "input")]
+    pub seed: Option<u64>,
+    /// Size of the generated input, in the unit `aoc generate` reports.
+    /// Defaults to the size of the real puzzle input.
+    #[arg(long, requires = "seed")]
+    pub size: Option<usize>,
+    /// How to print the results.
+    #[arg(long, value_enum, default_value_t)]
+    pub format: Format,
+}
+
+#[derive(Debug, Args)]
+pub struct SubmitArgs {
+    /// Day to submit, as a number or crate name.
+    pub day: String,
+    /// Part to submit (1 or 2).
+    pub part: Part,
+    /// Answer to submit. Defaults to solving the day on its cached input,
+    /// which is downloaded first if necessary.
+    pub answer: Option<String>,
+}
+
+#[derive(Debug, Args)]
+pub struct FetchArgs {
+    /// Day to fetch, as a number or crate name.
+    #[arg(required_unless_present = "all")]
+    pub day: Option<String>,
+    /// Fetch every day.
+    #[arg(long, conflicts_with = "day")]
+    pub all: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct NewArgs {
+    /// Number of the day.
+    pub day: u8,
+    /// Title of the puzzle, which also names the solution type.
+    pub title: String,
+}
+
+#[derive(Debug, Args)]
+pub struct WatchArgs {
+    /// Day to watch, as a number or crate name.
+    pub day: String,
+    /// Only run this part (1 or 2).
+    #[arg(long)]
+    pub part: Option<Part>,
+    /// Input file to use instead of the day's sample. It is watched as well.
+    #[arg(long)]
+    pub input: Option<PathBuf>,
+    /// Build with optimizations.
+    #[arg(long)]
+    pub release: bool,
+    /// Milliseconds between looking for changes.
+    #[arg(long, default_value_t = 500)]
+    pub interval: u64,
+}
+
+#[derive(Debug, Args)]
+pub struct GenerateArgs {
+    /// Day to generate an input for, as a number or crate name.
+    pub day: String,
+    /// Seed of the input. The same seed and size always give the same input.
+    #[arg(long, default_value_t = 0)]
+    pub seed: u64,
+    /// Size of the input. Defaults to the size of the real puzzle input.
+    #[arg(long)]
+    pub size: Option<usize>,
+    /// File to write the input to instead of standard output.
+    #[arg(long)]
+    pub output: Option<PathBuf>,
+}
+
+#[derive(Debug, Args)]
+pub struct CheckArgs {
+    /// Day to check, as a number or crate name.
+    #[arg(required_unless_present = "all")]
+    pub day: Option<String>,
+    /// Check every day.
+    #[arg(long, conflicts_with = "day")]
+    pub all: bool,
+    /// Number of generated inputs, from seed 0 on.
+    #[arg(long, default_value_t = 100)]
+    pub seeds: u64,
+    /// Size of the generated inputs. Defaults to a small size that keeps
+    /// disagreements readable.
+    #[arg(long)]
+    pub size: Option<usize>,
+}
+
+#[derive(Debug, Args)]
+pub struct ReportArgs {
+    /// File to write the page to.
+    #[arg(long, default_value = "report.html")]
+    pub output: PathBuf,
+    /// Run on the cached puzzle inputs instead of the samples.
+    #[arg(long, conflicts_with = "seed")]
+    pub cached: bool,
+    /// Run on inputs generated from this seed instead of the samples.
+    #[arg(long)]
+    pub seed: Option<u64>,
+    /// Size of the generated inputs. Defaults to the size of the real puzzle
+    /// inputs.
+    #[arg(long, requires = "seed")]
+    pub size: Option<usize>,
+}
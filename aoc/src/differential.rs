@@ -0,0 +1,393 @@
+//! Differential testing of the implementations of a day against each other.
+//!
+//! A day registers other implementations of its parts through
+//! [`Solution::variants`], such as a naive reference solver. Checking a day
+//! solves its sample and a range of generated inputs with every one of them
+//! and reports each input they disagree on, shrunk to the smallest input that
+//! still makes them disagree.
+
+use crate::registry::Day;
+use crate::runner::{self, Phase, RunError};
+use aoc_common::{Part, Solution};
+use std::collections::HashSet;
+use std::fmt;
+use std::io::{self, Write};
+use std::ops::Range;
+
+/// Size of the generated inputs unless the day's inputs are smaller anyway.
+/// Small inputs keep the reference solvers fast and failing inputs readable.
+pub const DEFAULT_SIZE: usize = 12;
+
+/// The answer of one variant next to the answer of the day's solution.
+#[derive(Debug)]
+pub struct Comparison {
+    pub variant: &'static str,
+    pub part: Part,
+    /// Answer of the day's solution.
+    pub expected: String,
+    /// Answer of the variant, or its panic.
+    pub actual: Result<String, RunError>,
+}
+
+impl Comparison {
+    #[must_use]
+    pub fn agrees(&self) -> bool {
+        self.actual
+            .as_ref()
+            .is_ok_and(|actual| *actual == self.expected)
+    }
+}
+
+/// Parses `input` and solves every part that has variants, with the day's
+/// solution and with each of them.
+///
+/// # Errors
+///
+/// Returns a [`RunError`] if the input cannot be parsed, or if parsing or
+/// the day's solution panics.
+pub(crate) fn compare<S: Solution>(input: &str) -> Result<Vec<Comparison>, RunError> {
+    let input = runner::catch(Phase::Parse, || S::parse(input))?
+        .map_err(|error| RunError::Parse(Box::new(error)))?;
+    let variants = S::variants();
+
+    let mut comparisons = Vec::with_capacity(variants.len());
+    for part in Part::ALL {
+        let mut part_variants = variants
+            .iter()
+            .filter(|variant| variant.part == part)
+            .peekable();
+        if part_variants.peek().is_none() {
+            continue;
+        }
+
+        let expected = runner::catch(Phase::Part(part), || S::solve(&input, part))?;
+        comparisons.extend(part_variants.map(|variant| Comparison {
+            variant: variant.name,
+            part,
+            expected: expected.clone(),
+            actual: runner::catch(Phase::Part(part), || (variant.solve)(&input)),
+        }));
+    }
+
+    Ok(comparisons)
+}
+
+/// Where a checked input came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Origin {
+    Sample,
+    Generated { seed: u64, size: usize },
+}
+
+impl fmt::Display for Origin {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Sample => formatter.pad("the sample"),
+            Self::Generated { seed, size } => write!(formatter, "seed {seed}, size {size}"),
+        }
+    }
+}
+
+/// An input the implementations of a day disagree on.
+#[derive(Debug)]
+pub struct Disagreement {
+    /// The input the disagreement was found on. For a generated input, the
+    /// size is the smallest one that still disagrees.
+    pub origin: Origin,
+    /// The smallest input found that still disagrees.
+    pub input: String,
+    /// The comparisons that failed on it, or why the day's own solution
+    /// could not solve it.
+    pub failures: Result<Vec<Comparison>, RunError>,
+}
+
+/// The outcome of checking one day.
+#[derive(Debug)]
+pub struct Check {
+    pub day: &'static Day,
+    /// The variants of the day with the part each one solves.
+    pub variants: Vec<(&'static str, Part)>,
+    /// Number of inputs every variant was run on.
+    pub inputs: usize,
+    pub disagreements: Vec<Disagreement>,
+}
+
+impl Check {
+    #[must_use]
+    pub fn is_success(&self) -> bool {
+        self.disagreements.is_empty()
+    }
+}
+
+/// Whether `input` makes the implementations disagree. Inputs the day
+/// cannot parse don't count, so shrinking keeps inputs valid.
+fn fails(day: &Day, input: &str) -> bool {
+    match day.compare(input) {
+        Ok(comparisons) => !comparisons.iter().all(Comparison::agrees),
+        Err(RunError::Panicked { .. }) => true,
+        Err(RunError::Input(_) | RunError::Parse(_)) => false,
+    }
+}
+
+/// Whether `outcome` holds a failure that has not been reported yet. Failures
+/// of the day's own solution are only reported once.
+fn has_new_failures(
+    outcome: &Result<Vec<Comparison>, RunError>,
+    reported: &HashSet<Option<(&'static str, Part)>>,
+) -> bool {
+    match outcome {
+        Ok(comparisons) => comparisons.iter().any(|comparison| {
+            !comparison.agrees() && !reported.contains(&Some((comparison.variant, comparison.part)))
+        }),
+        Err(_) => !reported.contains(&None),
+    }
+}
+
+/// Compares the implementations of `day` on its sample and on inputs
+/// generated from `seeds`, of `size` or [`DEFAULT_SIZE`].
+///
+/// Every disagreement is shrunk and reported once per variant, so a broken
+/// variant doesn't report every seed.
+#[must_use]
+pub fn check(day: &'static Day, seeds: Range<u64>, size: Option<usize>) -> Check {
+    let input_size = size.unwrap_or_else(|| day.default_size.min(DEFAULT_SIZE));
+    let variants = day.variants();
+    if variants.is_empty() {
+        return Check {
+            day,
+            variants,
+            inputs: 0,
+            disagreements: Vec::new(),
+        };
+    }
+
+    let inputs = [(Origin::Sample, runner::read_input(day, None))]
+        .into_iter()
+        .chain(seeds.map(|seed| {
+            let origin = Origin::Generated {
+                seed,
+                size: input_size,
+            };
+            (origin, Ok(day.generate(seed, Some(input_size))))
+        }));
+
+    let mut reported = HashSet::new();
+    let mut disagreements = Vec::new();
+    let mut checked = 0;
+    for (origin, input) in inputs {
+        checked += 1;
+        let input = match input {
+            Ok(input) => input,
+            Err(error) => {
+                disagreements.push(Disagreement {
+                    origin,
+                    input: String::new(),
+                    failures: Err(error),
+                });
+                continue;
+            }
+        };
+        if !has_new_failures(&day.compare(&input), &reported) {
+            continue;
+        }
+
+        let (origin, input) = match origin {
+            Origin::Generated {
+                seed,
+                size: generated_size,
+            } => {
+                let smallest = (1..generated_size)
+                    .find(|&smaller| fails(day, &day.generate(seed, Some(smaller))))
+                    .unwrap_or(generated_size);
+                let generated = day.generate(seed, Some(smallest));
+                (
+                    Origin::Generated {
+                        seed,
+                        size: smallest,
+                    },
+                    generated,
+                )
+            }
+            Origin::Sample => (origin, input),
+        };
+        let input = shrink(&input, |candidate| fails(day, candidate));
+        let failures = day.compare(&input).map(|comparisons| {
+            comparisons
+                .into_iter()
+                .filter(|comparison| !comparison.agrees())
+                .collect::<Vec<_>>()
+        });
+
+        match &failures {
+            Ok(comparisons) => reported.extend(
+                comparisons
+                    .iter()
+                    .map(|comparison| Some((comparison.variant, comparison.part))),
+            ),
+            Err(_) => {
+                reported.insert(None);
+            }
+        }
+        disagreements.push(Disagreement {
+            origin,
+            input,
+            failures,
+        });
+    }
+
+    Check {
+        day,
+        variants,
+        inputs: checked,
+        disagreements,
+    }
+}
+
+/// Removes runs of lines from `input`, or of characters if it is a single
+/// line, as long as it keeps failing. Longer runs are tried first, so
+/// whole records go before their single lines.
+pub fn shrink(input: &str, fails: impl Fn(&str) -> bool) -> String {
+    let trimmed = input.trim_end();
+    let (mut units, end) = if trimmed.contains('\n') {
+        (input.split_inclusive('\n').collect::<Vec<_>>(), "")
+    } else {
+        let end = input.strip_prefix(trimmed).unwrap_or_default();
+        (trimmed.matches(|_: char| true).collect::<Vec<_>>(), end)
+    };
+    let join = |kept: &[&str]| format!("{}{end}", kept.concat());
+
+    loop {
+        let before = units.len();
+        for run in (1..=units.len() / 2).rev() {
+            let mut start = 0;
+            while start + run <= units.len() && run < units.len() {
+                let remaining = units
+                    .iter()
+                    .enumerate()
+                    .filter(|&(index, _)| !(start..start + run).contains(&index))
+                    .map(|(_, &unit)| unit)
+                    .collect::<Vec<_>>();
+                if fails(&join(&remaining)) {
+                    units = remaining;
+                } else {
+                    start += 1;
+                }
+            }
+        }
+        if units.len() == before {
+            return join(&units);
+        }
+    }
+}
+
+/// Writes one line per day and every disagreement with its input.
+///
+/// # Errors
+///
+/// Returns any error from writing to `out`.
+pub fn write_check(out: &mut impl Write, check: &Check) -> io::Result<()> {
+    let label = format!("{:>2} {}", check.day.number, check.day.name);
+    if check.variants.is_empty() {
+        return writeln!(out, "{label:<11} no variants");
+    }
+
+    let variants = check.variants.len();
+    write!(
+        out,
+        "{label:<11} {variants} variant{} on {} inputs: ",
+        if variants == 1 { "" } else { "s" },
+        check.inputs
+    )?;
+    match check.disagreements.len() {
+        0 => writeln!(out, "all agree")?,
+        1 => writeln!(out, "1 disagreement")?,
+        count => writeln!(out, "{count} disagreements")?,
+    }
+
+    for disagreement in &check.disagreements {
+        writeln!(out, "  on {}:", disagreement.origin)?;
+        match &disagreement.failures {
+            Ok(comparisons) => {
+                for comparison in comparisons {
+                    let actual = match &comparison.actual {
+                        Ok(actual) => actual.clone(),
+                        Err(error) => error.to_string(),
+                    };
+                    writeln!(
+                        out,
+                        "    part {} {}: {actual}, expected {}",
+                        comparison.part, comparison.variant, comparison.expected
+                    )?;
+                }
+            }
+            Err(error) => writeln!(out, "    {error}")?,
+        }
+        for line in disagreement.input.lines() {
+            writeln!(out, "    | {line}")?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::{self, DAYS};
+
+    #[test]
+    fn test_shrink() {
+        let input = "1\n2\nbad\n3\n4\n";
+        assert_eq!(
+            shrink(input, |candidate| candidate.contains("bad")),
+            "bad\n"
+        );
+
+        let both_digits = |candidate: &str| candidate.contains('5') && candidate.contains('7');
+        assert_eq!(shrink("12345678\n", both_digits), "57\n");
+
+        // Inputs that pass are left alone
+        assert_eq!(shrink(input, |_| false), input);
+    }
+
+    #[test]
+    fn test_variants_agree() {
+        for day in DAYS {
+            let check = check(day, 0..3, None);
+            let mut report = Vec::new();
+            write_check(&mut report, &check).expect("writing to a vector");
+            assert!(check.is_success(), "{}", String::from_utf8_lossy(&report));
+        }
+    }
+
+    #[test]
+    fn test_reports_disagreement() {
+        let day = registry::find("one").expect("day one is registered");
+        let comparisons = day.compare("3 4\n4 3\n2 5\n").expect("a valid input");
+        assert_eq!(comparisons.len(), day.variants().len());
+        assert!(comparisons.iter().all(Comparison::agrees));
+
+        let mut report = Vec::new();
+        let check = Check {
+            day,
+            variants: day.variants(),
+            inputs: 1,
+            disagreements: vec![Disagreement {
+                origin: Origin::Generated { seed: 4, size: 1 },
+                input: "3 4\n".to_owned(),
+                failures: Ok(vec![Comparison {
+                    variant: "nested loops",
+                    part: Part::Two,
+                    expected: "0".to_owned(),
+                    actual: Ok("12".to_owned()),
+                }]),
+            }],
+        };
+        write_check(&mut report, &check).expect("writing to a vector");
+        assert_eq!(
+            String::from_utf8_lossy(&report),
+            " 1 one      1 variant on 1 inputs: 1 disagreement\n  \
+             on seed 4, size 1:\n    part 2 nested loops: 12, expected 0\n    | 3 4\n"
+        );
+    }
+}
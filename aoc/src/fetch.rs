@@ -0,0 +1,390 @@
+//! Downloading puzzle inputs from the Advent of Code website.
+//!
+//! Every input is cached under [`CACHE_DIR`] in the workspace, keyed by year
+//! and day, and never downloaded again once it is there. Downloading needs
+//! the `session` cookie of a logged-in browser, taken from the
+//! [`SESSION_VAR`] environment variable or the [`SESSION_FILE`] in the
+//! workspace. Both the cache and the session file are git-ignored.
+
+use crate::registry::Day;
+use std::env;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use ureq::{Agent, AgentBuilder, Error as RequestError, Transport};
+
+/// The year all days belong to.
+pub const YEAR: u16 = 2024;
+pub const BASE_URL: &str = "https://adventofcode.com";
+/// Environment variable holding the session token.
+pub const SESSION_VAR: &str = "AOC_SESSION";
+/// File in the workspace holding the session token, used if [`SESSION_VAR`]
+/// is not set.
+pub const SESSION_FILE: &str = ".session";
+/// Directory in the workspace the downloaded inputs are cached in.
+pub const CACHE_DIR: &str = "inputs";
+
+/// Identifies the tool to the Advent of Code servers, as their maintainer
+/// asks automated tools to do.
+const USER_AGENT: &str = concat!(
+    "aoc_24 runner ",
+    env!("CARGO_PKG_VERSION"),
+    " (+https://github.com/grouvie/aoc_24)"
+);
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+/// The root of the workspace the runner was built from.
+#[must_use]
+pub fn workspace_dir() -> &'static Path {
+    let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    manifest_dir.parent().unwrap_or(manifest_dir)
+}
+
+/// Looks up the session token in [`SESSION_VAR`], then in `file`.
+///
+/// # Errors
+///
+/// Returns a [`FetchError::Io`] if the file exists but cannot be read.
+pub fn load_session(file: &Path) -> Result<Option<String>, FetchError> {
+    let token = match env::var(SESSION_VAR) {
+        Ok(token) => token,
+        Err(_) => match fs::read_to_string(file) {
+            Ok(token) => token,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(source) => {
+                return Err(FetchError::Io {
+                    path: file.to_owned(),
+                    source,
+                })
+            }
+        },
+    };
+    let token = token.trim();
+
+    Ok((!token.is_empty()).then(|| token.to_owned()))
+}
+
+/// Downloaded inputs on disk.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cache {
+    root: PathBuf,
+}
+
+impl Cache {
+    #[must_use]
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// Where the input of `day` in `year` is cached.
+    #[must_use]
+    pub fn path(&self, year: u16, day: u8) -> PathBuf {
+        self.root
+            .join(year.to_string())
+            .join(format!("{day:02}.txt"))
+    }
+
+    fn store(&self, year: u16, day: u8, contents: &str) -> Result<PathBuf, FetchError> {
+        let path = self.path(year, day);
+        let io_error = |source| FetchError::Io {
+            path: path.clone(),
+            source,
+        };
+
+        if let Some(directory) = path.parent() {
+            fs::create_dir_all(directory).map_err(io_error)?;
+        }
+        // Write the whole input before it becomes visible, so an interrupted
+        // download never leaves a truncated input in the cache
+        let partial = path.with_extension("part");
+        fs::write(&partial, contents).map_err(io_error)?;
+        fs::rename(&partial, &path).map_err(io_error)?;
+
+        Ok(path)
+    }
+}
+
+impl Default for Cache {
+    fn default() -> Self {
+        Self::new(workspace_dir().join(CACHE_DIR))
+    }
+}
+
+/// An HTTP client for the Advent of Code website, authenticated with a
+/// session token.
+#[derive(Debug, Clone)]
+pub struct Client {
+    base_url: String,
+    session: Option<String>,
+    agent: Agent,
+}
+
+impl Client {
+    #[must_use]
+    pub fn new(base_url: impl Into<String>, session: Option<String>) -> Self {
+        Self {
+            base_url: base_url.into(),
+            session,
+            agent: AgentBuilder::new()
+                .user_agent(USER_AGENT)
+                .timeout(TIMEOUT)
+                .build(),
+        }
+    }
+
+    /// A client for the real website using the workspace's session.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`FetchError`] if the session file cannot be read.
+    pub fn from_workspace() -> Result<Self, FetchError> {
+        let session = load_session(&workspace_dir().join(SESSION_FILE))?;
+        Ok(Self::new(BASE_URL, session))
+    }
+
+    fn cookie(&self) -> Result<String, FetchError> {
+        let session = self.session.as_deref().ok_or(FetchError::MissingSession)?;
+        Ok(format!("session={session}"))
+    }
+
+    /// Requests `path` and returns the body of the response.
+    pub(crate) fn get(&self, path: &str) -> Result<String, FetchError> {
+        let url = format!("{}{path}", self.base_url);
+        let response = self
+            .agent
+            .get(&url)
+            .set("Cookie", &self.cookie()?)
+            .call()
+            .map_err(|error| FetchError::request(&url, error))?;
+
+        response
+            .into_string()
+            .map_err(|source| FetchError::Response { url, source })
+    }
+
+    /// Posts `form` to `path` and returns the body of the response.
+    pub(crate) fn post_form(
+        &self,
+        path: &str,
+        form: &[(&str, &str)],
+    ) -> Result<String, FetchError> {
+        let url = format!("{}{path}", self.base_url);
+        let response = self
+            .agent
+            .post(&url)
+            .set("Cookie", &self.cookie()?)
+            .send_form(form)
+            .map_err(|error| FetchError::request(&url, error))?;
+
+        response
+            .into_string()
+            .map_err(|source| FetchError::Response { url, source })
+    }
+}
+
+/// A cached input and whether it had to be downloaded first.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fetched {
+    pub path: PathBuf,
+    pub downloaded: bool,
+}
+
+/// Downloads inputs into a [`Cache`].
+#[derive(Debug)]
+pub struct Fetcher {
+    client: Client,
+    cache: Cache,
+}
+
+impl Fetcher {
+    #[must_use]
+    pub fn new(client: Client, cache: Cache) -> Self {
+        Self { client, cache }
+    }
+
+    /// A fetcher for the real website using the workspace's cache and
+    /// session.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`FetchError`] if the session file cannot be read.
+    pub fn from_workspace() -> Result<Self, FetchError> {
+        Ok(Self::new(Client::from_workspace()?, Cache::default()))
+    }
+
+    #[must_use]
+    pub fn cache(&self) -> &Cache {
+        &self.cache
+    }
+
+    /// The path of the input of `day`, downloading it unless it is cached.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`FetchError`] if the input is not cached and cannot be
+    /// downloaded or stored.
+    pub fn fetch(&self, year: u16, day: &Day) -> Result<Fetched, FetchError> {
+        let path = self.cache.path(year, day.number);
+        if path.is_file() {
+            return Ok(Fetched {
+                path,
+                downloaded: false,
+            });
+        }
+
+        let contents = self
+            .client
+            .get(&format!("/{year}/day/{}/input", day.number))?;
+
+        Ok(Fetched {
+            path: self.cache.store(year, day.number, &contents)?,
+            downloaded: true,
+        })
+    }
+}
+
+#[derive(Debug)]
+pub enum FetchError {
+    /// No session token is configured.
+    MissingSession,
+    /// The server answered with an error status.
+    Status {
+        url: String,
+        status: u16,
+    },
+    /// The server could not be reached.
+    Transport {
+        url: String,
+        source: Box<Transport>,
+    },
+    /// The response could not be read.
+    Response {
+        url: String,
+        source: io::Error,
+    },
+    Io {
+        path: PathBuf,
+        source: io::Error,
+    },
+}
+
+impl FetchError {
+    fn request(url: &str, error: RequestError) -> Self {
+        match error {
+            RequestError::Status(status, _) => Self::Status {
+                url: url.to_owned(),
+                status,
+            },
+            RequestError::Transport(transport) => Self::Transport {
+                url: url.to_owned(),
+                source: Box::new(transport),
+            },
+        }
+    }
+}
+
+impl fmt::Display for FetchError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingSession => write!(
+                formatter,
+                "no session token, set {SESSION_VAR} or write it to {}",
+                workspace_dir().join(SESSION_FILE).display()
+            ),
+            Self::Status { url, status } => {
+                write!(formatter, "{url} answered with status {status}")
+            }
+            Self::Transport { url, .. } => write!(formatter, "failed to request {url}"),
+            Self::Response { url, .. } => {
+                write!(formatter, "failed to read the response from {url}")
+            }
+            Self::Io { path, .. } => write!(formatter, "failed to access {}", path.display()),
+        }
+    }
+}
+
+impl Error for FetchError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::MissingSession | Self::Status { .. } => None,
+            Self::Transport { source, .. } => Some(source.as_ref()),
+            Self::Response { source, .. } | Self::Io { source, .. } => Some(source),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry;
+    use crate::stand_in::{Request, StandIn};
+
+    #[test]
+    fn test_fetch_caches_input() {
+        let stand_in = StandIn::serve(200, "3   4\n4   3\n");
+        let cache_dir = tempfile::tempdir().expect("a temporary directory");
+        let client = Client::new(&stand_in.base_url, Some("secret".to_owned()));
+        let fetcher = Fetcher::new(client, Cache::new(cache_dir.path()));
+        let day = registry::find("1").expect("day one is registered");
+
+        let first = fetcher.fetch(YEAR, day).expect("the input is downloaded");
+        let second = fetcher.fetch(YEAR, day).expect("the input is cached");
+
+        assert!(first.downloaded);
+        assert!(!second.downloaded);
+        assert_eq!(first.path, cache_dir.path().join("2024").join("01.txt"));
+        assert_eq!(second.path, first.path);
+        assert_eq!(
+            fs::read_to_string(&first.path).ok().as_deref(),
+            Some("3   4\n4   3\n")
+        );
+        assert_eq!(
+            stand_in.requests(),
+            [Request {
+                method: "GET".to_owned(),
+                url: "/2024/day/1/input".to_owned(),
+                cookie: Some("session=secret".to_owned()),
+                body: String::new(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_fetch_errors_are_not_cached() {
+        let stand_in = StandIn::serve(404, "Please don't repeatedly request this endpoint");
+        let cache_dir = tempfile::tempdir().expect("a temporary directory");
+        let cache = Cache::new(cache_dir.path());
+        let day = registry::find("5").expect("day five is registered");
+
+        let anonymous = Fetcher::new(Client::new(&stand_in.base_url, None), cache.clone());
+        assert!(matches!(
+            anonymous.fetch(YEAR, day),
+            Err(FetchError::MissingSession)
+        ));
+        assert!(stand_in.requests().is_empty());
+
+        let client = Client::new(&stand_in.base_url, Some("secret".to_owned()));
+        let fetcher = Fetcher::new(client, cache);
+        assert!(matches!(
+            fetcher.fetch(YEAR, day),
+            Err(FetchError::Status { status: 404, .. })
+        ));
+        assert!(!fetcher.cache().path(YEAR, 5).exists());
+    }
+
+    #[test]
+    fn test_load_session() {
+        let directory = tempfile::tempdir().expect("a temporary directory");
+        let file = directory.path().join(SESSION_FILE);
+
+        // The environment variable takes precedence over the file
+        if env::var_os(SESSION_VAR).is_none() {
+            assert_eq!(load_session(&file).ok(), Some(None));
+            fs::write(&file, "  abc123\n").expect("the session file is written");
+            assert_eq!(load_session(&file).ok(), Some(Some("abc123".to_owned())));
+        }
+    }
+}
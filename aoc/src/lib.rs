@@ -0,0 +1,21 @@
+//! Runs the solutions of every day through a common interface.
+
+pub mod answers;
+pub mod cli;
+pub mod differential;
+pub mod fetch;
+pub mod output;
+pub mod registry;
+pub mod report;
+pub mod runner;
+pub mod scaffold;
+#[cfg(test)]
+mod stand_in;
+pub mod submit;
+pub mod watch;
+
+pub use registry::{Day, DAYS};
+
+// Only used by the benchmarks.
+#[cfg(test)]
+use criterion as _;
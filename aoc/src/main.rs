@@ -0,0 +1,344 @@
+#![allow(
+    unused_crate_dependencies,
+    reason = "the dependencies are used through the library target"
+)]
+
+use aoc::cli::{
+    CheckArgs, Cli, Command, FetchArgs, GenerateArgs, NewArgs, ReportArgs, RunArgs, SubmitArgs,
+    WatchArgs,
+};
+use aoc::differential;
+use aoc::fetch::{self, Cache, Fetcher, CACHE_DIR};
+use aoc::output;
+use aoc::registry::{self, DAYS};
+use aoc::report::{self, RunHistory, REPORT_DIR, RUN_HISTORY};
+use aoc::runner::{self, DayReport};
+use aoc::scaffold::{self, NewDay};
+use aoc::submit::{Submitter, Verdict};
+use aoc::watch::Watch;
+use aoc::Day;
+#[cfg(feature = "memory")]
+use aoc_common::memory::CountingAllocator;
+use aoc_common::parallel::Parallelism;
+use aoc_common::Part;
+use clap::Parser;
+use std::error::Error;
+use std::fs;
+use std::io::{self, Write};
+use std::panic;
+use std::process::ExitCode;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+#[cfg(feature = "memory")]
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+fn run(args: &RunArgs) -> io::Result<ExitCode> {
+    let parts = args.part.map_or(Part::ALL.to_vec(), |part| vec![part]);
+
+    let run_day = |day: &'static Day| match args.seed {
+        Some(seed) => runner::run_generated(day, &parts, seed, args.size),
+        None => runner::run_day(day, &parts, args.input.as_deref()),
+    };
+
+    let reports = if args.all {
+        DAYS.iter().map(run_day).collect::<Vec<_>>()
+    } else {
+        let query = args.day.as_deref().unwrap_or_default();
+        let Some(day) = registry::find(query) else {
+            writeln!(io::stderr().lock(), "aoc: unknown day {query:?}")?;
+            return Ok(ExitCode::FAILURE);
+        };
+        vec![run_day(day)]
+    };
+
+    output::write_reports(&mut io::stdout().lock(), args.format, &reports)?;
+
+    Ok(if reports.iter().all(DayReport::is_success) {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    })
+}
+
+fn fetch(args: &FetchArgs) -> io::Result<ExitCode> {
+    let days = if args.all {
+        DAYS.iter().collect::<Vec<_>>()
+    } else {
+        let query = args.day.as_deref().unwrap_or_default();
+        let Some(day) = registry::find(query) else {
+            writeln!(io::stderr().lock(), "aoc: unknown day {query:?}")?;
+            return Ok(ExitCode::FAILURE);
+        };
+        vec![day]
+    };
+
+    let fetcher = match Fetcher::from_workspace() {
+        Ok(fetcher) => fetcher,
+        Err(error) => {
+            writeln!(io::stderr().lock(), "aoc: {error}")?;
+            return Ok(ExitCode::FAILURE);
+        }
+    };
+
+    let mut status = ExitCode::SUCCESS;
+    for day in days {
+        match fetcher.fetch(fetch::YEAR, day) {
+            Ok(input) => writeln!(
+                io::stdout().lock(),
+                "{:>2} {:<8}  {}{}",
+                day.number,
+                day.name,
+                input.path.display(),
+                if input.downloaded { "" } else { " (cached)" }
+            )?,
+            Err(error) => {
+                writeln!(io::stderr().lock(), "aoc: day {}: {error}", day.number)?;
+                status = ExitCode::FAILURE;
+            }
+        }
+    }
+
+    Ok(status)
+}
+
+/// Solves `part` of `day` on its cached input, downloading it if necessary.
+fn solve_cached(day: &'static Day, part: Part) -> Result<String, Box<dyn Error>> {
+    let input = Fetcher::from_workspace()?.fetch(fetch::YEAR, day)?;
+    let report = runner::run_day(day, &[part], Some(&input.path));
+    let mut solved = report.outcome?;
+    let result = solved.parts.pop().ok_or("the part was not solved")??;
+
+    Ok(result.answer)
+}
+
+fn submit(args: &SubmitArgs) -> io::Result<ExitCode> {
+    let Some(day) = registry::find(&args.day) else {
+        writeln!(io::stderr().lock(), "aoc: unknown day {:?}", args.day)?;
+        return Ok(ExitCode::FAILURE);
+    };
+
+    let outcome = args
+        .answer
+        .clone()
+        .map_or_else(|| solve_cached(day, args.part), Ok)
+        .and_then(|answer| {
+            let mut submitter = Submitter::from_workspace()?;
+            Ok(submitter.submit(fetch::YEAR, day, args.part, &answer)?)
+        });
+
+    match outcome {
+        Ok(attempt) => {
+            let mut stdout = io::stdout().lock();
+            write!(
+                stdout,
+                "Day {} part {}: {} is {}",
+                day.number, args.part, attempt.answer, attempt.verdict
+            )?;
+            if let Some(wait) = attempt.wait {
+                write!(stdout, ", wait {wait}s before the next attempt")?;
+            }
+            writeln!(stdout)?;
+
+            Ok(if attempt.verdict == Verdict::Correct {
+                ExitCode::SUCCESS
+            } else {
+                ExitCode::FAILURE
+            })
+        }
+        Err(error) => {
+            writeln!(io::stderr().lock(), "aoc: {error}")?;
+            Ok(ExitCode::FAILURE)
+        }
+    }
+}
+
+fn new(args: &NewArgs) -> io::Result<ExitCode> {
+    let created = NewDay::new(args.day, &args.title)
+        .and_then(|new_day| scaffold::create(fetch::workspace_dir(), &new_day));
+
+    match created {
+        Ok(directory) => {
+            writeln!(io::stdout().lock(), "Created {}", directory.display())?;
+            Ok(ExitCode::SUCCESS)
+        }
+        Err(error) => {
+            writeln!(io::stderr().lock(), "aoc: {error}")?;
+            Ok(ExitCode::FAILURE)
+        }
+    }
+}
+
+fn watch(args: &WatchArgs, parallelism: Parallelism) -> io::Result<ExitCode> {
+    let Some(day) = registry::find(&args.day) else {
+        writeln!(io::stderr().lock(), "aoc: unknown day {:?}", args.day)?;
+        return Ok(ExitCode::FAILURE);
+    };
+
+    let watch = Watch::new(
+        day,
+        args.part.map_or(Part::ALL.to_vec(), |part| vec![part]),
+        args.input.as_deref(),
+        args.release,
+        Duration::from_millis(args.interval),
+        parallelism,
+    );
+    let error = match watch.and_then(|watch| watch.watch(&mut io::stdout())) {
+        Ok(()) => return Ok(ExitCode::SUCCESS),
+        Err(error) => error,
+    };
+    writeln!(io::stderr().lock(), "aoc: {error}")?;
+    Ok(ExitCode::FAILURE)
+}
+
+fn generate(args: &GenerateArgs) -> io::Result<ExitCode> {
+    let Some(day) = registry::find(&args.day) else {
+        writeln!(io::stderr().lock(), "aoc: unknown day {:?}", args.day)?;
+        return Ok(ExitCode::FAILURE);
+    };
+
+    let size = args.size.unwrap_or(day.default_size);
+    let input = day.generate(args.seed, Some(size));
+    match &args.output {
+        Some(path) => {
+            if let Err(error) = fs::write(path, input) {
+                writeln!(io::stderr().lock(), "aoc: {}: {error}", path.display())?;
+                return Ok(ExitCode::FAILURE);
+            }
+            writeln!(
+                io::stdout().lock(),
+                "Wrote {size} {} from seed {} to {}",
+                day.size,
+                args.seed,
+                path.display()
+            )?;
+        }
+        None => io::stdout().lock().write_all(input.as_bytes())?,
+    }
+
+    Ok(ExitCode::SUCCESS)
+}
+
+fn check(args: &CheckArgs) -> io::Result<ExitCode> {
+    let days = if args.all {
+        DAYS.iter().collect::<Vec<_>>()
+    } else {
+        let query = args.day.as_deref().unwrap_or_default();
+        let Some(day) = registry::find(query) else {
+            writeln!(io::stderr().lock(), "aoc: unknown day {query:?}")?;
+            return Ok(ExitCode::FAILURE);
+        };
+        vec![day]
+    };
+
+    // Shrinking reruns panicking inputs many times, and the report shows
+    // every panic message already
+    panic::set_hook(Box::new(|_| {}));
+
+    let mut status = ExitCode::SUCCESS;
+    for day in days {
+        let check = differential::check(day, 0..args.seeds, args.size);
+        differential::write_check(&mut io::stdout().lock(), &check)?;
+        if !check.is_success() {
+            status = ExitCode::FAILURE;
+        }
+    }
+
+    Ok(status)
+}
+
+fn report(args: &ReportArgs) -> io::Result<ExitCode> {
+    let cache = Cache::new(fetch::workspace_dir().join(CACHE_DIR));
+    let (entries, description) = match args.seed {
+        Some(seed) => (
+            DAYS.iter()
+                .map(|day| report::run_day(day, Ok(day.generate(seed, args.size))))
+                .collect::<Vec<_>>(),
+            format!("Inputs generated from seed {seed}."),
+        ),
+        None if args.cached => (
+            DAYS.iter()
+                .map(|day| {
+                    let path = cache.path(fetch::YEAR, day.number);
+                    report::run_day(day, runner::read_input(day, Some(&path)))
+                })
+                .collect(),
+            "Cached puzzle inputs.".to_owned(),
+        ),
+        None => (
+            DAYS.iter()
+                .map(|day| report::run_day(day, runner::read_input(day, None)))
+                .collect(),
+            "Sample inputs.".to_owned(),
+        ),
+    };
+    let history = RunHistory::load(fetch::workspace_dir().join(REPORT_DIR).join(RUN_HISTORY))
+        .and_then(|mut history| {
+            let now = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |elapsed| elapsed.as_secs());
+            let reports = entries
+                .iter()
+                .map(|entry| &entry.report)
+                .collect::<Vec<_>>();
+            history.record(&reports, now)?;
+            Ok(history)
+        });
+    let history = match history {
+        Ok(history) => history,
+        Err(error) => {
+            writeln!(io::stderr().lock(), "aoc: {error}")?;
+            return Ok(ExitCode::FAILURE);
+        }
+    };
+
+    let mut page = Vec::new();
+    report::write_html(&mut page, &entries, &history, &description)?;
+    if let Err(error) = fs::write(&args.output, page) {
+        writeln!(
+            io::stderr().lock(),
+            "aoc: {}: {error}",
+            args.output.display()
+        )?;
+        return Ok(ExitCode::FAILURE);
+    }
+    writeln!(
+        io::stdout().lock(),
+        "Wrote report to {}",
+        args.output.display()
+    )?;
+
+    Ok(if entries.iter().all(|entry| entry.report.is_success()) {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    })
+}
+
+fn main() -> io::Result<ExitCode> {
+    let cli = Cli::parse();
+    // Every subcommand may run the parallel days, and the pool can only be
+    // configured before the first of them
+    let parallelism = match cli.parallelism() {
+        Ok(parallelism) => parallelism,
+        Err(error) => {
+            writeln!(io::stderr().lock(), "aoc: {error}")?;
+            return Ok(ExitCode::FAILURE);
+        }
+    };
+    if let Err(error) = parallelism.configure() {
+        writeln!(io::stderr().lock(), "aoc: {error}")?;
+        return Ok(ExitCode::FAILURE);
+    }
+
+    match cli.command {
+        Command::Run(args) => run(&args),
+        Command::Fetch(args) => fetch(&args),
+        Command::Submit(args) => submit(&args),
+        Command::New(args) => new(&args),
+        Command::Watch(args) => watch(&args, parallelism),
+        Command::Generate(args) => generate(&args),
+        Command::Check(args) => check(&args),
+        Command::Report(args) => report(&args),
+    }
+}
@@ -0,0 +1,286 @@
+//! Writes run results for people and for scripts.
+
+use crate::runner::{DayReport, Phase, RunError};
+use aoc_common::memory::Allocations;
+use aoc_common::record::{self, nanos, MemoryRecord, Record};
+use clap::ValueEnum;
+use std::io::{self, Write};
+use std::time::Duration;
+
+/// How to print the results of a run.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    /// A table of answers and timings.
+    #[default]
+    Text,
+    /// A JSON array with one record per part.
+    Json,
+}
+
+/// Writes `reports` in `format`.
+///
+/// # Errors
+///
+/// Returns any error from writing to `out`.
+pub fn write_reports(
+    out: &mut impl Write,
+    format: Format,
+    reports: &[DayReport],
+) -> io::Result<()> {
+    match format {
+        Format::Text => write_summary(out, reports),
+        Format::Json => write_json(out, reports),
+    }
+}
+
+/// Writes a table with one row per phase and the total time, and what
+/// every phase allocated if allocations were counted.
+///
+/// # Errors
+///
+/// Returns any error from writing to `out`.
+pub fn write_summary(out: &mut impl Write, reports: &[DayReport]) -> io::Result<()> {
+    let answer_width = reports
+        .iter()
+        .filter_map(|report| report.outcome.as_ref().ok())
+        .flat_map(|solved| &solved.parts)
+        .filter_map(|result| result.as_ref().ok())
+        .map(|result| result.answer.len())
+        .chain([6])
+        .max()
+        .unwrap_or_default();
+    let counting = reports
+        .iter()
+        .filter_map(|report| report.outcome.as_ref().ok())
+        .any(|solved| solved.parse_allocations.is_some());
+    let memory_width = if counting { MEMORY_WIDTH } else { 0 };
+    let rule = "-".repeat(answer_width + 34 + memory_width);
+
+    write!(
+        out,
+        "{:<11} {:>5}  {:<answer_width$}  {:>12}",
+        "Day", "Part", "Answer", "Time"
+    )?;
+    if counting {
+        write!(
+            out,
+            "  {:>9}  {:>10}  {:>10}",
+            "Allocs", "Allocated", "Peak"
+        )?;
+    }
+    writeln!(out)?;
+    writeln!(out, "{rule}")?;
+
+    let mut total = Duration::ZERO;
+    let mut total_allocations = Allocations::default();
+    for report in reports {
+        let label = format!("{:>2} {}", report.day.number, report.day.name);
+        let solved = match &report.outcome {
+            Ok(solved) => solved,
+            Err(error) => {
+                writeln!(out, "{label:<11} {:>5}  error: {error}", "-")?;
+                continue;
+            }
+        };
+
+        total += solved.parse_duration;
+        write!(
+            out,
+            "{label:<11} {:>5}  {:<answer_width$}  {:>12.2?}",
+            Phase::Parse,
+            "",
+            solved.parse_duration
+        )?;
+        write_memory_columns(out, solved.parse_allocations.as_ref())?;
+        add_allocations(&mut total_allocations, solved.parse_allocations.as_ref());
+
+        for result in &solved.parts {
+            match result {
+                Ok(result) => {
+                    total += result.duration;
+                    write!(
+                        out,
+                        "{label:<11} {:>5}  {:<answer_width$}  {:>12.2?}",
+                        result.part, result.answer, result.duration
+                    )?;
+                    write_memory_columns(out, result.allocations.as_ref())?;
+                    add_allocations(&mut total_allocations, result.allocations.as_ref());
+                }
+                Err(error) => writeln!(out, "{label:<11} {:>5}  error: {error}", "-")?,
+            }
+        }
+    }
+
+    writeln!(out, "{rule}")?;
+    write!(
+        out,
+        "{:<11} {:>5}  {:<answer_width$}  {total:>12.2?}",
+        "Total", "", ""
+    )?;
+    write_memory_columns(out, counting.then_some(&total_allocations))
+}
+
+/// Width of the allocation columns of the summary.
+const MEMORY_WIDTH: usize = 37;
+
+/// Ends a row of the summary, with the allocation columns if `allocations`
+/// were counted.
+fn write_memory_columns(out: &mut impl Write, allocations: Option<&Allocations>) -> io::Result<()> {
+    let Some(allocations) = allocations else {
+        return writeln!(out);
+    };
+    writeln!(
+        out,
+        "  {:>9}  {:>10}  {:>10}",
+        allocations.count,
+        format_bytes(allocations.bytes),
+        format_bytes(allocations.peak_bytes)
+    )
+}
+
+/// Adds up allocations counts and bytes and keeps the highest peak.
+fn add_allocations(total: &mut Allocations, allocations: Option<&Allocations>) {
+    if let Some(allocations) = allocations {
+        total.count += allocations.count;
+        total.bytes += allocations.bytes;
+        total.peak_bytes = total.peak_bytes.max(allocations.peak_bytes);
+    }
+}
+
+/// `bytes` in the largest binary unit that keeps at least one whole unit.
+#[must_use]
+pub fn format_bytes(bytes: usize) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+    #[expect(
+        clippy::cast_precision_loss,
+        reason = "three significant digits are shown at most"
+    )]
+    let mut value = bytes as f64 / 1024.0;
+    let mut units = UNITS.iter();
+    let mut unit = units.next().unwrap_or(&"KiB");
+    while value >= 1024.0 {
+        let Some(next) = units.next() else {
+            break;
+        };
+        value /= 1024.0;
+        unit = next;
+    }
+    format!("{value:.1} {unit}")
+}
+
+/// Flattens `report` into one record per part.
+#[must_use]
+pub fn records(report: &DayReport) -> Vec<Record> {
+    let record = |part: Option<u8>, error: Option<&RunError>| Record {
+        day: report.day.number,
+        name: report.day.name,
+        part,
+        answer: None,
+        error: error.map(ToString::to_string),
+        parse_nanos: None,
+        solve_nanos: None,
+        parse_memory: None,
+        solve_memory: None,
+        input_sha256: report.input_hash.clone(),
+    };
+
+    let solved = match &report.outcome {
+        Ok(solved) => solved,
+        Err(error) => return vec![record(None, Some(error))],
+    };
+
+    solved
+        .parts
+        .iter()
+        .map(|result| match result {
+            Ok(result) => Record {
+                answer: Some(result.answer.clone()),
+                parse_nanos: Some(nanos(solved.parse_duration)),
+                solve_nanos: Some(nanos(result.duration)),
+                parse_memory: solved.parse_allocations.as_ref().map(MemoryRecord::from),
+                solve_memory: result.allocations.as_ref().map(MemoryRecord::from),
+                ..record(Some(result.part.number()), None)
+            },
+            Err(error) => Record {
+                parse_nanos: Some(nanos(solved.parse_duration)),
+                parse_memory: solved.parse_allocations.as_ref().map(MemoryRecord::from),
+                ..record(failed_part(error), Some(error))
+            },
+        })
+        .collect()
+}
+
+fn failed_part(error: &RunError) -> Option<u8> {
+    match error {
+        RunError::Panicked {
+            phase: Phase::Part(part),
+            ..
+        } => Some(part.number()),
+        _ => None,
+    }
+}
+
+/// Writes the records of all `reports` as a JSON array.
+///
+/// # Errors
+///
+/// Returns any error from writing to `out`.
+pub fn write_json(out: &mut impl Write, reports: &[DayReport]) -> io::Result<()> {
+    let records = reports.iter().flat_map(records).collect::<Vec<_>>();
+    record::write_json(out, &records)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry;
+    use crate::runner::run_day;
+    use aoc_common::Part;
+    use std::path::Path;
+
+    #[test]
+    fn test_records() {
+        let day = registry::find("one").expect("day one is registered");
+
+        let solved = records(&run_day(day, &[Part::Two], None));
+        let summary = solved
+            .iter()
+            .map(|record| {
+                (
+                    record.part,
+                    record.answer.as_deref(),
+                    record.error.is_some(),
+                    record.solve_nanos.is_some(),
+                    record.input_sha256.as_ref().map(String::len),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(summary, [(Some(2), Some("31"), false, true, Some(64))]);
+
+        let missing = records(&run_day(day, &Part::ALL, Some(Path::new("missing.txt"))));
+        let failure = missing
+            .iter()
+            .map(|record| {
+                (
+                    record.part,
+                    record.error.is_some(),
+                    record.input_sha256.is_some(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(failure, [(None, true, false)]);
+    }
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(0), "0 B");
+        assert_eq!(format_bytes(1023), "1023 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(25 * 1024 * 1024), "25.0 MiB");
+        assert_eq!(format_bytes(usize::MAX), "16777216.0 TiB");
+    }
+}
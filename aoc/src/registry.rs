@@ -0,0 +1,214 @@
+//! The days known to the runner.
+
+use crate::differential::{self, Comparison};
+use crate::runner::{self, RunError, Solved};
+use aoc_common::generate::Generate;
+use aoc_common::input::Sample;
+use aoc_common::render::Canvas;
+use aoc_common::{Part, Solution};
+use std::marker::PhantomData;
+use std::path::Path;
+
+/// A day's parsed input, ready to solve either part.
+pub trait Parsed {
+    fn solve(&self, part: Part) -> String;
+
+    /// The day's pictures of the input or its solution.
+    fn canvases(&self) -> Vec<Canvas>;
+}
+
+struct ParsedInput<S: Solution> {
+    input: S::Input,
+    solution: PhantomData<S>,
+}
+
+impl<S: Solution> Parsed for ParsedInput<S> {
+    fn solve(&self, part: Part) -> String {
+        S::solve(&self.input, part)
+    }
+
+    fn canvases(&self) -> Vec<Canvas> {
+        S::canvases(&self.input)
+    }
+}
+
+fn parse<S: Solution + 'static>(input: &str) -> Result<Box<dyn Parsed>, RunError> {
+    match S::parse(input) {
+        Ok(input) => Ok(Box::new(ParsedInput::<S> {
+            input,
+            solution: PhantomData,
+        })),
+        Err(error) => Err(RunError::Parse(Box::new(error))),
+    }
+}
+
+/// A day's solution as seen by the runner.
+#[derive(Debug, Clone, Copy)]
+pub struct Day {
+    pub number: u8,
+    pub name: &'static str,
+    pub title: &'static str,
+    /// Input used when none is given on the command line.
+    pub sample: Sample,
+    /// What the size of a generated input counts.
+    pub size: &'static str,
+    /// Size of generated inputs unless another is asked for, close to the
+    /// real puzzle input.
+    pub default_size: usize,
+    parse: fn(&str) -> Result<Box<dyn Parsed>, RunError>,
+    generate: fn(u64, usize) -> String,
+    variants: fn() -> Vec<(&'static str, Part)>,
+    compare: fn(&str) -> Result<Vec<Comparison>, RunError>,
+}
+
+fn variants<S: Solution>() -> Vec<(&'static str, Part)> {
+    S::variants()
+        .into_iter()
+        .map(|variant| (variant.name, variant.part))
+        .collect()
+}
+
+impl Day {
+    const fn new<S: Generate + 'static>(name: &'static str, sample: Sample) -> Self {
+        Self {
+            number: S::DAY,
+            name,
+            title: S::TITLE,
+            sample,
+            size: S::SIZE,
+            default_size: S::DEFAULT_SIZE,
+            parse: parse::<S>,
+            generate: S::generate_seeded,
+            variants: variants::<S>,
+            compare: differential::compare::<S>,
+        }
+    }
+
+    /// Directory of the day's crate, which holds its example inputs.
+    #[must_use]
+    pub fn directory(&self) -> &'static Path {
+        Path::new(self.sample.path)
+            .parent()
+            .unwrap_or_else(|| Path::new("."))
+    }
+
+    /// Parses `input` without solving anything yet.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RunError::Parse`] if the input cannot be parsed.
+    pub fn parse(&self, input: &str) -> Result<Box<dyn Parsed>, RunError> {
+        (self.parse)(input)
+    }
+
+    /// A synthetic input from `seed`, of `size` or the default size.
+    #[must_use]
+    pub fn generate(&self, seed: u64, size: Option<usize>) -> String {
+        (self.generate)(seed, size.unwrap_or(self.default_size))
+    }
+
+    /// Names of the other implementations of the day's parts, with the part
+    /// each one solves.
+    #[must_use]
+    pub fn variants(&self) -> Vec<(&'static str, Part)> {
+        (self.variants)()
+    }
+
+    /// Solves every part that has variants on `input`, with the day's
+    /// solution and with every variant.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`RunError`] if the input cannot be parsed or the day's
+    /// solution panics.
+    pub fn compare(&self, input: &str) -> Result<Vec<Comparison>, RunError> {
+        (self.compare)(input)
+    }
+
+    /// The day's pictures of `input` or its solution.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RunError::Parse`] if the input cannot be parsed.
+    pub fn canvases(&self, input: &str) -> Result<Vec<Canvas>, RunError> {
+        Ok(self.parse(input)?.canvases())
+    }
+
+    /// Parses `input` and solves `parts` of the puzzle.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`RunError`] if the input cannot be parsed.
+    pub fn solve(&self, input: &str, parts: &[Part]) -> Result<Solved, RunError> {
+        runner::solve(self, input, parts)
+    }
+}
+
+macro_rules! day {
+    ($name:ident :: $solution:ident) => {
+        Day::new::<$name::$solution>(stringify!($name), $name::SAMPLE)
+    };
+}
+
+pub const DAYS: &[Day] = &[
+    day!(one::HistorianHysteria),
+    day!(two::RedNosedReports),
+    day!(three::MullItOver),
+    day!(four::CeresSearch),
+    day!(five::PrintQueue),
+    day!(six::GuardGallivant),
+    day!(seven::BridgeRepair),
+    day!(eight::ResonantCollinearity),
+    day!(nine::DiskFragmenter),
+    day!(ten::HoofIt),
+    day!(eleven::PlutonianPebbles),
+    day!(twelve::GardenGroups),
+    day!(thirteen::ClawContraption),
+    day!(fourteen::RestroomRedoubt),
+];
+
+/// Looks up a day by its number (`6`, `06`) or crate name (`six`).
+#[must_use]
+pub fn find(query: &str) -> Option<&'static Day> {
+    let query = query.trim();
+    match query.parse::<u8>() {
+        Ok(number) => DAYS.iter().find(|day| day.number == number),
+        Err(_) => DAYS.iter().find(|day| day.name.eq_ignore_ascii_case(query)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find() {
+        assert_eq!(find("6").map(|day| day.name), Some("six"));
+        assert_eq!(find("06").map(|day| day.name), Some("six"));
+        assert_eq!(find("Twelve").map(|day| day.number), Some(12));
+        assert!(find("26").is_none());
+        assert!(find("sixty").is_none());
+    }
+
+    #[test]
+    fn test_generate() {
+        for day in DAYS {
+            let input = day.generate(1, Some(day.default_size.min(20)));
+            assert_eq!(input, day.generate(1, Some(day.default_size.min(20))));
+            let solved = day.solve(&input, &Part::ALL);
+            assert!(
+                solved.is_ok_and(|solved| solved.parts.iter().all(Result::is_ok)),
+                "day {} solves the inputs it generates",
+                day.number
+            );
+        }
+    }
+
+    #[test]
+    fn test_days_are_ordered() {
+        assert!(DAYS.windows(2).all(|pair| match pair {
+            [first, second] => first.number < second.number,
+            _ => false,
+        }));
+    }
+}
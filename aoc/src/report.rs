@@ -0,0 +1,573 @@
+//! A self-contained HTML page with the results of every day.
+//!
+//! The page shows the same table as `aoc run`, a sparkline of earlier
+//! timings for every part and the renderings the days offer through
+//! [`Solution::canvases`](aoc_common::Solution::canvases), embedded as PNG
+//! images. Every report appends its timings to the [`RUN_HISTORY`] in the
+//! workspace's [`REPORT_DIR`], which the sparklines of later reports are
+//! drawn from. The directory is ignored by git like the cached inputs, but
+//! kept apart from them as it holds nothing downloaded.
+
+use crate::fetch::YEAR;
+use crate::output::format_bytes;
+use crate::registry::Day;
+use crate::runner::{self, DayReport, Phase, RunError};
+use aoc_common::image::{Export, ImageFormat, Palette};
+use aoc_common::memory::Allocations;
+use aoc_common::render::Canvas;
+use aoc_common::Part;
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Directory in the workspace for what the reports keep between runs.
+pub const REPORT_DIR: &str = "reports";
+/// File in the [`REPORT_DIR`] the timings of every report are appended to,
+/// one JSON object per line.
+pub const RUN_HISTORY: &str = "runs.jsonl";
+/// Number of earlier runs a sparkline shows at most.
+pub const HISTORY_LENGTH: usize = 30;
+/// Largest width or height of an embedded rendering in pixels.
+const IMAGE_SIZE: usize = 640;
+
+/// The timing of one part in the run history.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Timing {
+    /// Seconds since the Unix epoch.
+    pub recorded_at: u64,
+    pub day: u8,
+    pub part: u8,
+    /// Hex-encoded SHA-256 digest of the input, as timings of different
+    /// inputs don't compare.
+    pub input_sha256: String,
+    pub solve_nanos: u64,
+}
+
+/// The timings of earlier reports.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RunHistory {
+    path: PathBuf,
+    timings: Vec<Timing>,
+}
+
+impl RunHistory {
+    /// Reads the history at `path`, which does not have to exist yet.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`HistoryError`] if the history cannot be read or a line is
+    /// not a valid timing.
+    pub fn load(path: impl Into<PathBuf>) -> Result<Self, HistoryError> {
+        let path = path.into();
+        let contents = match fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(source) => return Err(HistoryError::Io { path, source }),
+        };
+
+        let timings = contents
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(index, line)| {
+                serde_json::from_str(line).map_err(|source| HistoryError::Format {
+                    path: path.clone(),
+                    line: index + 1,
+                    source,
+                })
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self { path, timings })
+    }
+
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The last [`HISTORY_LENGTH`] solve times of one part on one input,
+    /// oldest first.
+    #[must_use]
+    pub fn durations(&self, day: u8, part: u8, input_sha256: &str) -> Vec<Duration> {
+        let mut durations = self
+            .timings
+            .iter()
+            .rev()
+            .filter(|timing| {
+                timing.day == day && timing.part == part && timing.input_sha256 == input_sha256
+            })
+            .take(HISTORY_LENGTH)
+            .map(|timing| Duration::from_nanos(timing.solve_nanos))
+            .collect::<Vec<_>>();
+        durations.reverse();
+        durations
+    }
+
+    /// Appends the timing of every solved part of `reports`, recorded at
+    /// `now` in seconds since the Unix epoch.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`HistoryError::Io`] if the history cannot be written.
+    pub fn record(&mut self, reports: &[&DayReport], now: u64) -> Result<(), HistoryError> {
+        let timings = reports
+            .iter()
+            .filter_map(|&report| {
+                let input_sha256 = report.input_hash.clone()?;
+                let solved = report.outcome.as_ref().ok()?;
+                Some(
+                    solved
+                        .parts
+                        .iter()
+                        .filter_map(|result| result.as_ref().ok())
+                        .map(move |result| Timing {
+                            recorded_at: now,
+                            day: report.day.number,
+                            part: result.part.number(),
+                            input_sha256: input_sha256.clone(),
+                            solve_nanos: u64::try_from(result.duration.as_nanos())
+                                .unwrap_or(u64::MAX),
+                        }),
+                )
+            })
+            .flatten()
+            .collect::<Vec<_>>();
+
+        let io_error = |source| HistoryError::Io {
+            path: self.path.clone(),
+            source,
+        };
+        let lines = timings
+            .iter()
+            .map(|timing| serde_json::to_string(timing).map(|line| format!("{line}\n")))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(io::Error::from)
+            .map_err(io_error)?;
+
+        if let Some(directory) = self.path.parent() {
+            fs::create_dir_all(directory).map_err(io_error)?;
+        }
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .and_then(|mut file| file.write_all(lines.concat().as_bytes()))
+            .map_err(io_error)?;
+
+        self.timings.extend(timings);
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub enum HistoryError {
+    Io {
+        path: PathBuf,
+        source: io::Error,
+    },
+    /// A line of the history is not a valid timing.
+    Format {
+        path: PathBuf,
+        line: usize,
+        source: serde_json::Error,
+    },
+}
+
+impl fmt::Display for HistoryError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io { path, source } => write!(formatter, "{}: {source}", path.display()),
+            Self::Format { path, line, source } => {
+                write!(formatter, "{}:{line}: {source}", path.display())
+            }
+        }
+    }
+}
+
+impl Error for HistoryError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Io { source, .. } => Some(source),
+            Self::Format { source, .. } => Some(source),
+        }
+    }
+}
+
+/// What the report shows of one day.
+#[derive(Debug)]
+pub struct DayEntry {
+    pub report: DayReport,
+    pub canvases: Vec<Canvas>,
+}
+
+/// Runs every part of `day` on `input` and renders the day's pictures of
+/// it.
+#[must_use]
+pub fn run_day(day: &'static Day, input: Result<String, RunError>) -> DayEntry {
+    let canvases = input
+        .as_ref()
+        .ok()
+        .and_then(|input| day.canvases(input).ok())
+        .unwrap_or_default();
+
+    DayEntry {
+        report: runner::run_input(day, &Part::ALL, input),
+        canvases,
+    }
+}
+
+/// Writes the page for `entries`, with sparklines from `history`.
+/// `description` says which inputs the days ran on.
+///
+/// # Errors
+///
+/// Returns any error from writing to `out`.
+pub fn write_html(
+    out: &mut impl Write,
+    entries: &[DayEntry],
+    history: &RunHistory,
+    description: &str,
+) -> io::Result<()> {
+    let counting = entries
+        .iter()
+        .filter_map(|entry| entry.report.outcome.as_ref().ok())
+        .any(|solved| solved.parse_allocations.is_some());
+
+    writeln!(out, "<!DOCTYPE html>")?;
+    writeln!(out, r#"<html lang="en">"#)?;
+    writeln!(out, r#"<head><meta charset="utf-8">"#)?;
+    writeln!(out, "<title>Advent of Code {YEAR}</title>")?;
+    writeln!(out, "<style>{STYLE}</style>")?;
+    writeln!(out, "</head>")?;
+    writeln!(out, "<body>")?;
+    writeln!(out, "<h1>Advent of Code {YEAR}</h1>")?;
+    writeln!(out, "<p>{}</p>", escape(description))?;
+
+    writeln!(out, "<table>")?;
+    write!(
+        out,
+        "<thead><tr><th>Day</th><th>Part</th><th>Answer</th><th>Time</th><th>History</th>"
+    )?;
+    if counting {
+        write!(out, "<th>Allocs</th><th>Allocated</th><th>Peak</th>")?;
+    }
+    writeln!(out, "</tr></thead>")?;
+    writeln!(out, "<tbody>")?;
+    let mut total = Duration::ZERO;
+    for entry in entries {
+        total += write_rows(out, &entry.report, history, counting)?;
+    }
+    writeln!(out, "</tbody>")?;
+    writeln!(
+        out,
+        r#"<tfoot><tr><td colspan="3">Total</td><td class="number">{}</td></tr></tfoot>"#,
+        escape(&format!("{total:.2?}"))
+    )?;
+    writeln!(out, "</table>")?;
+
+    for entry in entries.iter().filter(|entry| !entry.canvases.is_empty()) {
+        let day = entry.report.day;
+        writeln!(
+            out,
+            r#"<section id="day-{}"><h2>Day {}: {}</h2>"#,
+            day.number,
+            day.number,
+            escape(day.title)
+        )?;
+        for canvas in &entry.canvases {
+            write_figure(out, canvas)?;
+        }
+        writeln!(out, "</section>")?;
+    }
+
+    writeln!(out, "</body>")?;
+    writeln!(out, "</html>")
+}
+
+const STYLE: &str = "\
+body { font-family: sans-serif; margin: 2em; background: #0f0f23; color: #cccccc; }
+h1, h2 { color: #ffff66; font-weight: normal; }
+table { border-collapse: collapse; }
+th, td { padding: 0.2em 0.8em; text-align: left; }
+thead th { border-bottom: 1px solid #666666; }
+tfoot td { border-top: 1px solid #666666; }
+td.number { text-align: right; font-variant-numeric: tabular-nums; }
+td.error { color: #ff6666; }
+tr.parse td { color: #888888; }
+svg.sparkline polyline { fill: none; stroke: #00cc00; stroke-width: 1.5; }
+figure { display: inline-block; margin: 0 1em 1em 0; }
+img { image-rendering: pixelated; }";
+
+/// Writes the rows of one day and returns the time it took.
+fn write_rows(
+    out: &mut impl Write,
+    report: &DayReport,
+    history: &RunHistory,
+    counting: bool,
+) -> io::Result<Duration> {
+    let day = report.day;
+    let label = format!(
+        r##"<a href="#day-{}">{:>2} {}</a>"##,
+        day.number,
+        day.number,
+        escape(day.name)
+    );
+    let solved = match &report.outcome {
+        Ok(solved) => solved,
+        Err(error) => {
+            writeln!(
+                out,
+                r#"<tr><td>{label}</td><td>-</td><td class="error" colspan="3">{}</td></tr>"#,
+                escape(&error.to_string())
+            )?;
+            return Ok(Duration::ZERO);
+        }
+    };
+
+    write!(
+        out,
+        r#"<tr class="parse"><td>{label}</td><td>{}</td><td></td><td class="number">{}</td><td></td>"#,
+        Phase::Parse,
+        escape(&format!("{:.2?}", solved.parse_duration))
+    )?;
+    write_memory_cells(out, counting, solved.parse_allocations.as_ref())?;
+
+    let mut total = solved.parse_duration;
+    for result in &solved.parts {
+        match result {
+            Ok(result) => {
+                total += result.duration;
+                let durations = report.input_hash.as_deref().map_or_else(Vec::new, |hash| {
+                    history.durations(day.number, result.part.number(), hash)
+                });
+                write!(
+                    out,
+                    r#"<tr><td>{label}</td><td>{}</td><td>{}</td><td class="number">{}</td><td>{}</td>"#,
+                    result.part,
+                    escape(&result.answer),
+                    escape(&format!("{:.2?}", result.duration)),
+                    sparkline(&durations)
+                )?;
+                write_memory_cells(out, counting, result.allocations.as_ref())?;
+            }
+            Err(error) => writeln!(
+                out,
+                r#"<tr><td>{label}</td><td>-</td><td class="error" colspan="3">{}</td></tr>"#,
+                escape(&error.to_string())
+            )?,
+        }
+    }
+
+    Ok(total)
+}
+
+/// Ends a row, with the allocation cells if allocations are counted at all.
+fn write_memory_cells(
+    out: &mut impl Write,
+    counting: bool,
+    allocations: Option<&Allocations>,
+) -> io::Result<()> {
+    match allocations {
+        _ if !counting => {}
+        None => write!(out, "<td></td><td></td><td></td>")?,
+        Some(allocations) => write!(
+            out,
+            r#"<td class="number">{}</td><td class="number">{}</td><td class="number">{}</td>"#,
+            allocations.count,
+            format_bytes(allocations.bytes),
+            format_bytes(allocations.peak_bytes)
+        )?,
+    }
+    writeln!(out, "</tr>")
+}
+
+/// An inline SVG line of `durations`, scaled from the fastest to the
+/// slowest, or nothing for fewer than two.
+#[must_use]
+pub fn sparkline(durations: &[Duration]) -> String {
+    const WIDTH: f64 = 120.0;
+    const HEIGHT: f64 = 20.0;
+
+    if durations.len() < 2 {
+        return String::new();
+    }
+    let seconds = durations
+        .iter()
+        .map(Duration::as_secs_f64)
+        .collect::<Vec<_>>();
+    let fastest = seconds.iter().copied().fold(f64::INFINITY, f64::min);
+    let slowest = seconds.iter().copied().fold(0.0, f64::max);
+    let range = (slowest - fastest).max(f64::EPSILON);
+    #[expect(
+        clippy::cast_precision_loss,
+        reason = "a sparkline has at most HISTORY_LENGTH points"
+    )]
+    let step = WIDTH / (seconds.len() - 1) as f64;
+
+    let points = seconds
+        .iter()
+        .zip(0_u32..)
+        .map(|(&time, index)| {
+            let x = f64::from(index) * step;
+            let y = HEIGHT - (time - fastest) / range * HEIGHT;
+            format!("{x:.1},{y:.1}")
+        })
+        .collect::<Vec<_>>()
+        .join(" ");
+    format!(
+        r#"<svg class="sparkline" width="{WIDTH}" height="{HEIGHT}" viewBox="-1 -1 {} {}"><title>{} runs, {:.2?} to {:.2?}</title><polyline points="{points}"/></svg>"#,
+        WIDTH + 2.0,
+        HEIGHT + 2.0,
+        durations.len(),
+        Duration::from_secs_f64(fastest),
+        Duration::from_secs_f64(slowest)
+    )
+}
+
+/// Writes `canvas` as an embedded PNG with its title as the caption.
+fn write_figure(out: &mut impl Write, canvas: &Canvas) -> io::Result<()> {
+    let export = Export {
+        path: PathBuf::new(),
+        format: ImageFormat::Png,
+        cell_size: (IMAGE_SIZE / canvas.width().max(canvas.height()).max(1)).clamp(1, 16),
+        palette: Palette::DARK,
+    };
+    let mut png = Vec::new();
+    export.write(&mut png, canvas)?;
+
+    let title = escape(&canvas.title);
+    writeln!(
+        out,
+        r#"<figure><img src="data:image/png;base64,{}" alt="{title}"><figcaption>{title}</figcaption></figure>"#,
+        base64(&png)
+    )
+}
+
+/// `text` with the characters that are special in HTML escaped.
+#[must_use]
+pub fn escape(text: &str) -> String {
+    text.chars()
+        .map(|char| match char {
+            '&' => "&amp;".to_owned(),
+            '<' => "&lt;".to_owned(),
+            '>' => "&gt;".to_owned(),
+            '"' => "&quot;".to_owned(),
+            '\'' => "&#39;".to_owned(),
+            _ => char.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .concat()
+}
+
+/// `bytes` in standard Base64 with padding.
+#[must_use]
+pub fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let symbol = |index: u32| {
+        usize::try_from(index & 0x3f)
+            .ok()
+            .and_then(|index| ALPHABET.get(index))
+            .map_or('=', |&symbol| char::from(symbol))
+    };
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let [first, second, third] = [0, 1, 2].map(|index| chunk.get(index).copied());
+        let group = [first, second, third]
+            .iter()
+            .fold(0, |group, byte| group << 8 | u32::from(byte.unwrap_or(0)));
+        encoded.push(symbol(group >> 18));
+        encoded.push(symbol(group >> 12));
+        encoded.push(if second.is_some() {
+            symbol(group >> 6)
+        } else {
+            '='
+        });
+        encoded.push(if third.is_some() { symbol(group) } else { '=' });
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry;
+
+    #[test]
+    fn test_base64() {
+        assert_eq!(base64(b""), "");
+        assert_eq!(base64(b"f"), "Zg==");
+        assert_eq!(base64(b"fo"), "Zm8=");
+        assert_eq!(base64(b"foo"), "Zm9v");
+        assert_eq!(base64(b"foobar"), "Zm9vYmFy");
+        assert_eq!(base64(&[0xff, 0xfe]), "//4=");
+    }
+
+    #[test]
+    fn test_escape() {
+        assert_eq!(
+            escape(r#"<a href="x">&'"#),
+            "&lt;a href=&quot;x&quot;&gt;&amp;&#39;"
+        );
+    }
+
+    #[test]
+    fn test_sparkline() {
+        assert_eq!(sparkline(&[Duration::from_millis(3)]), "");
+        let line = sparkline(&[Duration::from_millis(2), Duration::from_millis(4)]);
+        assert!(line.contains(r#"points="0.0,20.0 120.0,0.0""#), "{line}");
+    }
+
+    #[test]
+    fn test_history() {
+        let directory = tempfile::tempdir().expect("a temporary directory");
+        let path = directory.path().join(REPORT_DIR).join(RUN_HISTORY);
+        let day = registry::find("one").expect("day one is registered");
+        let entry = run_day(day, runner::read_input(day, None));
+
+        let mut history = RunHistory::load(&path).expect("a missing history is empty");
+        history
+            .record(&[&entry.report], 1)
+            .expect("the history is written");
+        history
+            .record(&[&entry.report], 2)
+            .expect("the history is written");
+
+        let reloaded = RunHistory::load(&path).expect("the history is read");
+        assert_eq!(reloaded, history);
+        let hash = entry.report.input_hash.expect("the sample is read");
+        assert_eq!(reloaded.durations(1, 1, &hash).len(), 2);
+        assert_eq!(reloaded.durations(1, 2, &hash).len(), 2);
+        assert!(reloaded.durations(1, 1, "other input").is_empty());
+    }
+
+    #[test]
+    fn test_write_html() {
+        let entries = ["one", "six"]
+            .map(|name| {
+                let day = registry::find(name).expect("the day is registered");
+                run_day(day, runner::read_input(day, None))
+            })
+            .into_iter()
+            .collect::<Vec<_>>();
+        let history = RunHistory {
+            path: PathBuf::new(),
+            timings: Vec::new(),
+        };
+
+        let mut page = Vec::new();
+        write_html(&mut page, &entries, &history, "Sample inputs").expect("writing to a vector");
+        let page = String::from_utf8(page).expect("the page is UTF-8");
+
+        assert!(page.starts_with("<!DOCTYPE html>"));
+        assert!(page.contains("<td>31</td>"), "day one's answer is shown");
+        assert!(page.contains(r#"<section id="day-6"><h2>Day 6: Guard Gallivant</h2>"#));
+        assert!(page.contains(r#"alt="Guard path""#));
+        assert!(!page.contains(r#"id="day-1""#), "day one has no renderings");
+    }
+}
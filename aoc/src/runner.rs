@@ -0,0 +1,240 @@
+//! Runs days and collects their answers and timings.
+
+use crate::registry::Day;
+use aoc_common::input::{InputError, Source};
+use aoc_common::memory::{Allocations, Measurement};
+use aoc_common::record::hash_input;
+use aoc_common::Part;
+use std::any::Any;
+use std::error::Error;
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// A step of solving a day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Phase {
+    Parse,
+    Part(Part),
+}
+
+impl fmt::Display for Phase {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Parse => formatter.pad("parse"),
+            Self::Part(part) => write!(formatter, "part {part}"),
+        }
+    }
+}
+
+/// The answer to one part of a day together with how long it took.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartResult {
+    pub day: u8,
+    pub part: Part,
+    pub answer: String,
+    pub duration: Duration,
+    /// What solving the part allocated, if allocations are counted.
+    pub allocations: Option<Allocations>,
+}
+
+/// The parts of a day solved from a single parsed input.
+#[derive(Debug)]
+pub struct Solved {
+    pub parse_duration: Duration,
+    /// What parsing allocated, if allocations are counted.
+    pub parse_allocations: Option<Allocations>,
+    pub parts: Vec<Result<PartResult, RunError>>,
+}
+
+#[derive(Debug)]
+pub enum RunError {
+    /// The input could not be read.
+    Input(InputError),
+    /// The input was rejected by the day's parser.
+    Parse(Box<dyn Error + Send + Sync>),
+    /// The solution panicked.
+    Panicked { phase: Phase, message: String },
+}
+
+impl fmt::Display for RunError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Input(source) => source.fmt(formatter),
+            Self::Parse(source) => write!(formatter, "invalid input: {source}"),
+            Self::Panicked { phase, message } => {
+                write!(formatter, "{phase} panicked: {message}")
+            }
+        }
+    }
+}
+
+impl Error for RunError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Input(source) => Some(source),
+            Self::Parse(source) => Some(source.as_ref()),
+            Self::Panicked { .. } => None,
+        }
+    }
+}
+
+/// The outcome of running some parts of one day.
+#[derive(Debug)]
+pub struct DayReport {
+    pub day: &'static Day,
+    /// SHA-256 digest of the input, if it could be read.
+    pub input_hash: Option<String>,
+    pub outcome: Result<Solved, RunError>,
+}
+
+impl DayReport {
+    #[must_use]
+    pub fn is_success(&self) -> bool {
+        self.outcome
+            .as_ref()
+            .is_ok_and(|solved| solved.parts.iter().all(Result::is_ok))
+    }
+}
+
+/// Reads the file at `path`, standard input for `-`, or the day's sample
+/// input if no path is given.
+///
+/// # Errors
+///
+/// Returns [`RunError::Input`] if the input cannot be read.
+pub fn read_input(day: &Day, path: Option<&Path>) -> Result<String, RunError> {
+    Source::from_arg(path.map(PathBuf::from), day.sample)
+        .read()
+        .map_err(RunError::Input)
+}
+
+/// Runs `action`, turning a panic into an error for `phase`.
+pub(crate) fn catch<T>(phase: Phase, action: impl FnOnce() -> T) -> Result<T, RunError> {
+    panic::catch_unwind(AssertUnwindSafe(action)).map_err(|payload| RunError::Panicked {
+        phase,
+        message: panic_message(payload.as_ref()),
+    })
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|message| (*message).to_owned())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic".to_owned())
+}
+
+/// Parses `input` once and solves `parts` of `day` from it, timing every
+/// phase.
+///
+/// # Errors
+///
+/// Returns a [`RunError`] if parsing fails or panics.
+pub fn solve(day: &Day, input: &str, parts: &[Part]) -> Result<Solved, RunError> {
+    let parse_measurement = Measurement::start();
+    let parse_start = Instant::now();
+    let parsed = catch(Phase::Parse, || day.parse(input))??;
+    let parse_duration = parse_start.elapsed();
+    let parse_allocations = parse_measurement.map(Measurement::finish);
+
+    let parts = parts
+        .iter()
+        .map(|&part| {
+            let measurement = Measurement::start();
+            let part_start = Instant::now();
+            let answer = catch(Phase::Part(part), || parsed.solve(part))?;
+            let duration = part_start.elapsed();
+
+            Ok(PartResult {
+                day: day.number,
+                part,
+                answer,
+                duration,
+                allocations: measurement.map(Measurement::finish),
+            })
+        })
+        .collect();
+
+    Ok(Solved {
+        parse_duration,
+        parse_allocations,
+        parts,
+    })
+}
+
+/// Runs `parts` of `day` on the input at `path` (`-` for standard input), or
+/// on its sample.
+#[must_use]
+pub fn run_day(day: &'static Day, parts: &[Part], path: Option<&Path>) -> DayReport {
+    run_input(day, parts, read_input(day, path))
+}
+
+/// Runs `parts` of `day` on an input generated from `seed`, of `size` or
+/// the day's default size.
+#[must_use]
+pub fn run_generated(
+    day: &'static Day,
+    parts: &[Part],
+    seed: u64,
+    size: Option<usize>,
+) -> DayReport {
+    run_input(day, parts, Ok(day.generate(seed, size)))
+}
+
+/// Runs `parts` of `day` on `input`, or reports why it could not be read.
+#[must_use]
+pub fn run_input(day: &'static Day, parts: &[Part], input: Result<String, RunError>) -> DayReport {
+    let (input_hash, outcome) = match input {
+        Ok(input) => (Some(hash_input(&input)), day.solve(&input, parts)),
+        Err(error) => (None, Err(error)),
+    };
+
+    DayReport {
+        day,
+        input_hash,
+        outcome,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry;
+
+    #[test]
+    fn test_run_day() {
+        let day = registry::find("one").expect("day one is registered");
+        let report = run_day(day, &Part::ALL, None);
+        let answers = report
+            .outcome
+            .as_ref()
+            .map(|solved| {
+                solved
+                    .parts
+                    .iter()
+                    .map(|result| result.as_ref().map(|result| result.answer.as_str()).ok())
+                    .collect::<Vec<_>>()
+            })
+            .ok();
+        assert_eq!(answers, Some(vec![Some("11"), Some("31")]));
+
+        let missing = run_day(day, &Part::ALL, Some(Path::new("does/not/exist.txt")));
+        assert!(!missing.is_success());
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let day = registry::find("eleven").expect("day eleven is registered");
+        let error = day.solve("125 x", &Part::ALL).map(|_| ()).err();
+        assert!(matches!(error, Some(RunError::Parse(_))));
+        assert_eq!(
+            error.map(|error| error.to_string()),
+            Some(
+                "invalid input: line 1, column 5: cannot parse \"x\": invalid digit found in string"
+                    .to_owned()
+            )
+        );
+    }
+}
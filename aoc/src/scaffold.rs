@@ -0,0 +1,402 @@
+//! Generating the crate of a new day.
+//!
+//! The crate is created from the templates in `aoc/templates/day` and then
+//! registered in the workspace members, the workspace dependencies, the
+//! runner's dependencies and [`DAYS`]. Every file is prepared before the
+//! first one is written, so a failure leaves the workspace untouched.
+
+use crate::registry::{Day, DAYS};
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Crate names of the days, which are named after their number.
+const NAMES: [&str; 25] = [
+    "one",
+    "two",
+    "three",
+    "four",
+    "five",
+    "six",
+    "seven",
+    "eight",
+    "nine",
+    "ten",
+    "eleven",
+    "twelve",
+    "thirteen",
+    "fourteen",
+    "fifteen",
+    "sixteen",
+    "seventeen",
+    "eighteen",
+    "nineteen",
+    "twenty",
+    "twenty_one",
+    "twenty_two",
+    "twenty_three",
+    "twenty_four",
+    "twenty_five",
+];
+
+/// Files of a new day, relative to its directory.
+const TEMPLATES: [(&str, &str); 5] = [
+    ("Cargo.toml", include_str!("../templates/day/Cargo.toml.in")),
+    (
+        "answers.toml",
+        include_str!("../templates/day/answers.toml.in"),
+    ),
+    ("sample.txt", ""),
+    ("src/lib.rs", include_str!("../templates/day/src/lib.rs.in")),
+    (
+        "src/main.rs",
+        include_str!("../templates/day/src/main.rs.in"),
+    ),
+];
+
+/// The day to create.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NewDay {
+    pub number: u8,
+    /// Crate name, such as `fifteen`.
+    pub name: &'static str,
+    pub title: String,
+    /// Name of the type implementing the solution, derived from the title.
+    pub solution: String,
+}
+
+impl NewDay {
+    /// Describes day `number` of the puzzle called `title`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ScaffoldError`] if there is no such day in Advent of Code
+    /// or the title cannot be turned into a type name.
+    pub fn new(number: u8, title: &str) -> Result<Self, ScaffoldError> {
+        let name = usize::from(number)
+            .checked_sub(1)
+            .and_then(|index| NAMES.get(index))
+            .ok_or(ScaffoldError::InvalidDay(number))?;
+        let title = title.trim();
+
+        let solution = title
+            .split(|char: char| !char.is_ascii_alphanumeric())
+            .flat_map(|word| {
+                let mut chars = word.chars();
+                chars
+                    .next()
+                    .map(|first| first.to_ascii_uppercase())
+                    .into_iter()
+                    .chain(chars)
+            })
+            .collect::<String>();
+        let is_literal_safe = !title.contains(['"', '\\', '\n']);
+        if !is_literal_safe || !solution.starts_with(|char: char| char.is_ascii_alphabetic()) {
+            return Err(ScaffoldError::InvalidTitle(title.to_owned()));
+        }
+
+        Ok(Self {
+            number,
+            name,
+            title: title.to_owned(),
+            solution,
+        })
+    }
+
+    fn render(&self, template: &str) -> String {
+        template
+            .replace("{{name}}", self.name)
+            .replace("{{number}}", &self.number.to_string())
+            .replace("{{title}}", &self.title)
+            .replace("{{solution}}", &self.solution)
+    }
+}
+
+/// A file listing every day, one line each.
+struct Registration {
+    /// Path relative to the workspace root.
+    file: &'static str,
+    /// Start of the line listing the day with the given crate name.
+    entry: fn(&str) -> String,
+    /// The line listing the new day.
+    line: fn(&NewDay) -> String,
+}
+
+const REGISTRATIONS: [Registration; 4] = [
+    Registration {
+        file: "Cargo.toml",
+        entry: |name| format!("    \"{name}\","),
+        line: |new_day| format!("    \"{}\",", new_day.name),
+    },
+    Registration {
+        file: "Cargo.toml",
+        entry: |name| format!("{name} = {{ path"),
+        line: |new_day| format!("{0} = {{ path = \"{0}\" }}", new_day.name),
+    },
+    Registration {
+        file: "aoc/Cargo.toml",
+        entry: |name| format!("{name}.workspace = true"),
+        line: |new_day| format!("{}.workspace = true", new_day.name),
+    },
+    Registration {
+        file: "aoc/src/registry.rs",
+        entry: |name| format!("    day!({name}::"),
+        line: |new_day| format!("    day!({}::{}),", new_day.name, new_day.solution),
+    },
+];
+
+/// Inserts the line of `new_day` next to the line of a neighbouring day, so
+/// the days stay in order.
+fn register(text: &str, new_day: &NewDay, registration: &Registration) -> Option<String> {
+    let mut lines = text.lines().collect::<Vec<_>>();
+    let position = |day: &Day| {
+        let entry = (registration.entry)(day.name);
+        lines.iter().position(|line| line.starts_with(&entry))
+    };
+
+    let previous = DAYS.iter().rev().find(|day| day.number < new_day.number);
+    let following = DAYS.iter().find(|day| day.number > new_day.number);
+    let index = match (previous, following) {
+        (Some(previous), _) => position(previous)? + 1,
+        (None, Some(following)) => position(following)?,
+        (None, None) => return None,
+    };
+    let line = (registration.line)(new_day);
+    lines.insert(index, &line);
+
+    let mut registered = lines.join("\n");
+    if text.ends_with('\n') {
+        registered.push('\n');
+    }
+    Some(registered)
+}
+
+/// Creates the crate of `new_day` in the workspace at `root` and registers
+/// it. Returns the directory of the new crate.
+///
+/// # Errors
+///
+/// Returns a [`ScaffoldError`] if the day already exists, a file listing the
+/// days has an unexpected layout, or a file cannot be read or written.
+pub fn create(root: &Path, new_day: &NewDay) -> Result<PathBuf, ScaffoldError> {
+    let directory = root.join(new_day.name);
+    if directory.exists() {
+        return Err(ScaffoldError::Exists(directory));
+    }
+    if DAYS.iter().any(|day| day.number == new_day.number) {
+        return Err(ScaffoldError::Registered(new_day.number));
+    }
+
+    // Prepare every change before writing anything
+    let mut edits = Vec::<(PathBuf, String)>::new();
+    for registration in &REGISTRATIONS {
+        let path = root.join(registration.file);
+        let text = match edits.iter().find(|(edited, _)| *edited == path) {
+            Some((_, text)) => text.clone(),
+            None => fs::read_to_string(&path).map_err(|source| ScaffoldError::Io {
+                path: path.clone(),
+                source,
+            })?,
+        };
+        let registered = register(&text, new_day, registration)
+            .ok_or_else(|| ScaffoldError::UnknownLayout { path: path.clone() })?;
+
+        edits.retain(|(edited, _)| *edited != path);
+        edits.push((path, registered));
+    }
+    let files = TEMPLATES
+        .iter()
+        .map(|(file, template)| (directory.join(file), new_day.render(template)));
+
+    for (path, contents) in files.chain(edits) {
+        let io_error = |source| ScaffoldError::Io {
+            path: path.clone(),
+            source,
+        };
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(io_error)?;
+        }
+        fs::write(&path, contents).map_err(io_error)?;
+    }
+
+    Ok(directory)
+}
+
+#[derive(Debug)]
+pub enum ScaffoldError {
+    /// Advent of Code has no day with this number.
+    InvalidDay(u8),
+    /// The title cannot be used as a string literal or a type name.
+    InvalidTitle(String),
+    /// The directory of the day already exists.
+    Exists(PathBuf),
+    /// A day with this number is already registered.
+    Registered(u8),
+    /// No line listing a neighbouring day was found in the file.
+    UnknownLayout {
+        path: PathBuf,
+    },
+    Io {
+        path: PathBuf,
+        source: io::Error,
+    },
+}
+
+impl fmt::Display for ScaffoldError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidDay(number) => write!(formatter, "there is no day {number}"),
+            Self::InvalidTitle(title) => write!(formatter, "invalid title {title:?}"),
+            Self::Exists(path) => write!(formatter, "{} already exists", path.display()),
+            Self::Registered(number) => write!(formatter, "day {number} is already registered"),
+            Self::UnknownLayout { path } => write!(
+                formatter,
+                "found no other day in {} to register the new one next to",
+                path.display()
+            ),
+            Self::Io { path, .. } => write!(formatter, "failed to access {}", path.display()),
+        }
+    }
+}
+
+impl Error for ScaffoldError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fetch;
+    use tempfile::TempDir;
+
+    /// A workspace holding copies of the files listing the days.
+    fn workspace() -> TempDir {
+        let root = tempfile::tempdir().expect("a temporary directory");
+        for registration in &REGISTRATIONS {
+            let path = root.path().join(registration.file);
+            fs::create_dir_all(path.parent().expect("the file is in a directory"))
+                .expect("the directory can be created");
+            fs::copy(fetch::workspace_dir().join(registration.file), path)
+                .expect("the file can be copied");
+        }
+        root
+    }
+
+    /// The first day that is not registered yet, so the tests keep working
+    /// as days are added, and the registered day it follows.
+    fn unregistered_day() -> (NewDay, &'static Day) {
+        let number = (1..=25)
+            .find(|number| DAYS.iter().all(|day| day.number != *number))
+            .expect("a day is left to solve");
+        let previous = DAYS
+            .iter()
+            .rev()
+            .find(|day| day.number < number)
+            .expect("day one is registered");
+        let new_day = NewDay::new(number, "Unsolved Puzzle").expect("a valid day");
+        (new_day, previous)
+    }
+
+    fn read(root: &TempDir, file: &str) -> String {
+        fs::read_to_string(root.path().join(file)).expect("the file exists")
+    }
+
+    #[test]
+    fn test_new_day() {
+        let new_day = NewDay::new(15, " Warehouse Woes ").expect("a valid day");
+        assert_eq!(new_day.name, "fifteen");
+        assert_eq!(new_day.title, "Warehouse Woes");
+        assert_eq!(new_day.solution, "WarehouseWoes");
+        assert_eq!(
+            NewDay::new(24, "Crossed-wires")
+                .map(|day| day.solution)
+                .ok(),
+            Some("CrossedWires".to_owned())
+        );
+
+        for (number, title) in [(0, "Title"), (26, "Title"), (15, "42"), (15, "A \"b\"")] {
+            assert!(
+                NewDay::new(number, title).ok().is_none(),
+                "{number} {title:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_create() {
+        let root = workspace();
+        let (new_day, previous) = unregistered_day();
+        let (name, previous_name) = (new_day.name, previous.name);
+        let directory = create(root.path(), &new_day).expect("the day is created");
+        assert_eq!(directory, root.path().join(name));
+
+        let lib = read(&root, &format!("{name}/src/lib.rs"));
+        assert!(lib.starts_with(&format!("//! Day {}: Unsolved Puzzle.\n", new_day.number)));
+        assert!(lib.contains(&format!(
+            "/// The [`Solution`] of day {}.\npub struct UnsolvedPuzzle;\n",
+            new_day.number
+        )));
+        assert!(lib.contains("impl Solution for UnsolvedPuzzle {"));
+        assert!(lib.contains("const TITLE: &'static str = \"Unsolved Puzzle\";"));
+        assert!(read(&root, &format!("{name}/Cargo.toml")).contains(&format!("name = \"{name}\"")));
+        assert!(read(&root, &format!("{name}/sample.txt")).is_empty());
+
+        let manifest = read(&root, "Cargo.toml");
+        assert!(manifest.contains(&format!("    \"{previous_name}\",\n    \"{name}\",\n")));
+        assert!(manifest.contains(&format!(
+            "{previous_name} = {{ path = \"{previous_name}\" }}\n{name} = {{ path = \"{name}\" }}\n"
+        )));
+        assert!(read(&root, "aoc/Cargo.toml").contains(&format!(
+            "{previous_name}.workspace = true\n{name}.workspace = true\n"
+        )));
+        let registry = read(&root, "aoc/src/registry.rs");
+        assert!(registry
+            .lines()
+            .zip(registry.lines().skip(1))
+            .any(
+                |(before, line)| before.starts_with(&format!("    day!({previous_name}::"))
+                    && line == format!("    day!({name}::UnsolvedPuzzle),")
+            ));
+    }
+
+    #[test]
+    fn test_refuse_existing() {
+        let root = workspace();
+        let (new_day, _) = unregistered_day();
+        fs::create_dir_all(root.path().join(new_day.name)).expect("the directory can be created");
+        let manifest = read(&root, "Cargo.toml");
+
+        assert!(matches!(
+            create(root.path(), &new_day),
+            Err(ScaffoldError::Exists(_))
+        ));
+        let registered = NewDay::new(3, "Mull It Over").expect("a valid day");
+        assert!(matches!(
+            create(root.path(), &registered),
+            Err(ScaffoldError::Registered(3))
+        ));
+        assert_eq!(read(&root, "Cargo.toml"), manifest);
+        assert!(!root.path().join("three").exists());
+    }
+
+    #[test]
+    fn test_unknown_layout() {
+        let root = workspace();
+        fs::write(root.path().join("aoc/Cargo.toml"), "[dependencies]\n")
+            .expect("the file can be written");
+        let (new_day, _) = unregistered_day();
+
+        assert!(matches!(
+            create(root.path(), &new_day),
+            Err(ScaffoldError::UnknownLayout { .. })
+        ));
+        assert!(!root.path().join(new_day.name).exists());
+    }
+}
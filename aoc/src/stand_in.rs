@@ -0,0 +1,82 @@
+//! A local stand-in for the Advent of Code website, so the HTTP clients can
+//! be tested offline.
+
+use std::sync::{Arc, Mutex};
+use std::thread;
+use tiny_http::{Header, Response, Server};
+
+/// A request the stand-in received.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Request {
+    pub(crate) method: String,
+    pub(crate) url: String,
+    pub(crate) cookie: Option<String>,
+    pub(crate) body: String,
+}
+
+/// Answers every request with the same response.
+#[derive(Debug)]
+pub(crate) struct StandIn {
+    pub(crate) base_url: String,
+    requests: Arc<Mutex<Vec<Request>>>,
+}
+
+impl StandIn {
+    /// Serves `body` with `status` on a free local port until the test
+    /// process exits.
+    pub(crate) fn serve(status: u16, body: &'static str) -> Self {
+        let server = Server::http("127.0.0.1:0").expect("a free local port");
+        let address = server
+            .server_addr()
+            .to_ip()
+            .expect("the server listens on an IP address");
+        let requests = Arc::<Mutex<Vec<Request>>>::default();
+        let recorded = Arc::clone(&requests);
+
+        thread::spawn(move || {
+            for mut request in server.incoming_requests() {
+                let cookie = request
+                    .headers()
+                    .iter()
+                    .find(|header| header.field.equiv("Cookie"))
+                    .map(|header| header.value.to_string());
+                let mut request_body = String::new();
+                request
+                    .as_reader()
+                    .read_to_string(&mut request_body)
+                    .expect("the request body is text");
+                recorded
+                    .lock()
+                    .expect("the test thread does not panic while holding the lock")
+                    .push(Request {
+                        method: request.method().to_string(),
+                        url: request.url().to_owned(),
+                        cookie,
+                        body: request_body,
+                    });
+
+                let content_type =
+                    Header::from_bytes("Content-Type", "text/html").expect("a valid header");
+                let response = Response::from_string(body)
+                    .with_status_code(status)
+                    .with_header(content_type);
+                request
+                    .respond(response)
+                    .expect("the client waits for the response");
+            }
+        });
+
+        Self {
+            base_url: format!("http://{address}"),
+            requests,
+        }
+    }
+
+    /// The requests received so far.
+    pub(crate) fn requests(&self) -> Vec<Request> {
+        self.requests
+            .lock()
+            .expect("the server thread does not panic while holding the lock")
+            .clone()
+    }
+}
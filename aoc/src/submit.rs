@@ -0,0 +1,591 @@
+//! Submitting answers to the Advent of Code website.
+//!
+//! Every attempt is appended to a local answer log in the workspace's
+//! [`SUBMISSION_DIR`]. Before anything is sent the log is consulted, so answers already
+//! known to be wrong, answers outside the known too-high/too-low bounds and
+//! submissions during a cooldown the server asked for are refused locally.
+
+use crate::fetch::{self, Client, FetchError};
+use crate::registry::Day;
+use aoc_common::Part;
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Directory in the workspace for what the submissions keep between runs.
+/// It is ignored by git like the cached inputs, but kept apart from them as
+/// it holds nothing downloaded.
+pub const SUBMISSION_DIR: &str = "submissions";
+/// File in the [`SUBMISSION_DIR`] every attempt is logged to, one JSON
+/// object per line.
+pub const ANSWER_LOG: &str = "answers.jsonl";
+
+/// How the server judged an answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Verdict {
+    Correct,
+    Incorrect,
+    TooHigh,
+    TooLow,
+    /// The answer was not checked because the last one was too recent.
+    RateLimited,
+    /// The part is already solved or not unlocked yet.
+    WrongLevel,
+    /// The response did not match any known message.
+    Unrecognized,
+}
+
+impl Verdict {
+    /// Whether the server checked the answer and rejected it.
+    #[must_use]
+    pub const fn is_wrong(self) -> bool {
+        matches!(self, Self::Incorrect | Self::TooHigh | Self::TooLow)
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str(match self {
+            Self::Correct => "correct",
+            Self::Incorrect => "incorrect",
+            Self::TooHigh => "too high",
+            Self::TooLow => "too low",
+            Self::RateLimited => "not checked, submitted too soon",
+            Self::WrongLevel => "not checked, the part is solved or locked",
+            Self::Unrecognized => "not recognized",
+        })
+    }
+}
+
+/// The parts of the server's answer that matter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Response {
+    pub verdict: Verdict,
+    /// How long to wait before the next submission, if the server said so.
+    pub wait: Option<Duration>,
+}
+
+/// Interprets the page the server returns after a submission.
+#[must_use]
+pub fn parse_response(body: &str) -> Response {
+    let verdict = if body.contains("That's the right answer") {
+        Verdict::Correct
+    } else if body.contains("answer is too high") {
+        Verdict::TooHigh
+    } else if body.contains("answer is too low") {
+        Verdict::TooLow
+    } else if body.contains("That's not the right answer") {
+        Verdict::Incorrect
+    } else if body.contains("You gave an answer too recently") {
+        Verdict::RateLimited
+    } else if body.contains("You don't seem to be solving the right level") {
+        Verdict::WrongLevel
+    } else {
+        Verdict::Unrecognized
+    };
+
+    Response {
+        verdict,
+        wait: parse_wait(body),
+    }
+}
+
+/// Finds either `You have 1m 5s left to wait` or `wait one minute`/`wait 5
+/// minutes` in the response.
+fn parse_wait(body: &str) -> Option<Duration> {
+    if let Some((_, rest)) = body.split_once("You have ") {
+        let (left, _) = rest.split_once(" left to wait")?;
+        return left
+            .split_whitespace()
+            .map(|amount| {
+                let unit = match amount.chars().last()? {
+                    'h' => 3600,
+                    'm' => 60,
+                    's' => 1,
+                    _ => return None,
+                };
+                let count = amount.trim_end_matches(char::is_alphabetic);
+                count.parse::<u64>().ok().map(|count| count * unit)
+            })
+            .sum::<Option<u64>>()
+            .map(Duration::from_secs);
+    }
+
+    let (_, rest) = body.split_once("wait ")?;
+    let mut words = rest.split_whitespace();
+    let minutes = match words.next()? {
+        "a" | "one" => 1,
+        count => count.parse::<u64>().ok()?,
+    };
+    words
+        .next()
+        .filter(|unit| unit.starts_with("minute"))
+        .map(|_| Duration::from_secs(minutes * 60))
+}
+
+/// A logged submission.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Attempt {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    pub answer: String,
+    pub verdict: Verdict,
+    /// Seconds since the Unix epoch.
+    pub submitted_at: u64,
+    /// Seconds to wait before the next submission. The server limits the
+    /// whole account, so this holds for every part of every day.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wait: Option<u64>,
+}
+
+/// Why an answer was not submitted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Refusal {
+    /// The part is already solved.
+    AlreadySolved { answer: String },
+    /// This exact answer was already rejected.
+    KnownWrong { verdict: Verdict },
+    /// The answer is at or beyond an answer known to be too high or too low.
+    OutOfBounds { bound: String, verdict: Verdict },
+    /// The server asked to wait before submitting again.
+    Cooldown { remaining: Duration },
+}
+
+impl fmt::Display for Refusal {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::AlreadySolved { answer } => {
+                write!(formatter, "the part is already solved with {answer}")
+            }
+            Self::KnownWrong { verdict } => {
+                write!(formatter, "this answer was already judged {verdict}")
+            }
+            Self::OutOfBounds { bound, verdict } => {
+                write!(formatter, "{bound} was already judged {verdict}")
+            }
+            Self::Cooldown { remaining } => write!(
+                formatter,
+                "the server asked to wait another {}s",
+                remaining.as_secs()
+            ),
+        }
+    }
+}
+
+/// Every attempt made so far.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AnswerLog {
+    path: PathBuf,
+    attempts: Vec<Attempt>,
+}
+
+impl AnswerLog {
+    /// Reads the log at `path`, which does not have to exist yet.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`SubmitError`] if the log cannot be read or a line is not
+    /// a valid attempt.
+    pub fn load(path: impl Into<PathBuf>) -> Result<Self, SubmitError> {
+        let path = path.into();
+        let contents = match fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(source) => return Err(SubmitError::LogIo { path, source }),
+        };
+
+        let attempts = contents
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(index, line)| {
+                serde_json::from_str(line).map_err(|source| SubmitError::LogFormat {
+                    path: path.clone(),
+                    line: index + 1,
+                    source,
+                })
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self { path, attempts })
+    }
+
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The attempts for one part, oldest first.
+    pub fn attempts(&self, year: u16, day: u8, part: Part) -> impl Iterator<Item = &Attempt> {
+        self.attempts.iter().filter(move |attempt| {
+            attempt.year == year && attempt.day == day && attempt.part == part.number()
+        })
+    }
+
+    /// Checks whether `answer` is worth submitting at `now`, in seconds since
+    /// the Unix epoch.
+    ///
+    /// # Errors
+    ///
+    /// Returns the [`Refusal`] if the log already rules the answer out.
+    pub fn check(
+        &self,
+        year: u16,
+        day: u8,
+        part: Part,
+        answer: &str,
+        now: u64,
+    ) -> Result<(), Refusal> {
+        if let Some(solved) = self
+            .attempts(year, day, part)
+            .find(|attempt| attempt.verdict == Verdict::Correct)
+        {
+            return Err(Refusal::AlreadySolved {
+                answer: solved.answer.clone(),
+            });
+        }
+
+        let number = answer.parse::<i128>().ok();
+        for attempt in self.attempts(year, day, part) {
+            if attempt.verdict.is_wrong() && attempt.answer == answer {
+                return Err(Refusal::KnownWrong {
+                    verdict: attempt.verdict,
+                });
+            }
+
+            let bound = attempt.answer.parse::<i128>().ok();
+            let out_of_bounds = match (attempt.verdict, number, bound) {
+                (Verdict::TooHigh, Some(number), Some(bound)) => number >= bound,
+                (Verdict::TooLow, Some(number), Some(bound)) => number <= bound,
+                _ => false,
+            };
+            if out_of_bounds {
+                return Err(Refusal::OutOfBounds {
+                    bound: attempt.answer.clone(),
+                    verdict: attempt.verdict,
+                });
+            }
+        }
+
+        // The server rate-limits the account, not the puzzle
+        let ready_at = self
+            .attempts
+            .iter()
+            .filter_map(|attempt| Some(attempt.submitted_at + attempt.wait?))
+            .max();
+        match ready_at {
+            Some(ready_at) if ready_at > now => Err(Refusal::Cooldown {
+                remaining: Duration::from_secs(ready_at - now),
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    /// Appends `attempt` to the log.
+    fn record(&mut self, attempt: Attempt) -> Result<(), SubmitError> {
+        let io_error = |source| SubmitError::LogIo {
+            path: self.path.clone(),
+            source,
+        };
+        let mut line = serde_json::to_string(&attempt)
+            .map_err(io::Error::from)
+            .map_err(io_error)?;
+        line.push('\n');
+
+        if let Some(directory) = self.path.parent() {
+            fs::create_dir_all(directory).map_err(io_error)?;
+        }
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .and_then(|mut file| file.write_all(line.as_bytes()))
+            .map_err(io_error)?;
+
+        self.attempts.push(attempt);
+        Ok(())
+    }
+}
+
+/// Submits answers and keeps the [`AnswerLog`].
+#[derive(Debug)]
+pub struct Submitter {
+    client: Client,
+    log: AnswerLog,
+}
+
+impl Submitter {
+    #[must_use]
+    pub fn new(client: Client, log: AnswerLog) -> Self {
+        Self { client, log }
+    }
+
+    /// A submitter for the real website using the workspace's session and
+    /// answer log.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`SubmitError`] if the session or the log cannot be read.
+    pub fn from_workspace() -> Result<Self, SubmitError> {
+        let log_path = fetch::workspace_dir().join(SUBMISSION_DIR).join(ANSWER_LOG);
+        Ok(Self::new(
+            Client::from_workspace()?,
+            AnswerLog::load(log_path)?,
+        ))
+    }
+
+    #[must_use]
+    pub fn log(&self) -> &AnswerLog {
+        &self.log
+    }
+
+    /// Submits `answer` for `part` of `day` unless the log rules it out, and
+    /// logs the outcome.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`SubmitError`] if the answer is refused, the request fails
+    /// or the attempt cannot be logged.
+    pub fn submit(
+        &mut self,
+        year: u16,
+        day: &Day,
+        part: Part,
+        answer: &str,
+    ) -> Result<Attempt, SubmitError> {
+        let answer = answer.trim();
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_secs());
+        self.log
+            .check(year, day.number, part, answer, now)
+            .map_err(SubmitError::Refused)?;
+
+        let level = part.number().to_string();
+        let body = self.client.post_form(
+            &format!("/{year}/day/{}/answer", day.number),
+            &[("level", &level), ("answer", answer)],
+        )?;
+        let response = parse_response(&body);
+
+        let attempt = Attempt {
+            year,
+            day: day.number,
+            part: part.number(),
+            answer: answer.to_owned(),
+            verdict: response.verdict,
+            submitted_at: now,
+            wait: response.wait.map(|wait| wait.as_secs()),
+        };
+        self.log.record(attempt.clone())?;
+
+        Ok(attempt)
+    }
+}
+
+#[derive(Debug)]
+pub enum SubmitError {
+    /// The answer log rules the answer out.
+    Refused(Refusal),
+    Request(FetchError),
+    LogIo {
+        path: PathBuf,
+        source: io::Error,
+    },
+    LogFormat {
+        path: PathBuf,
+        line: usize,
+        source: serde_json::Error,
+    },
+}
+
+impl From<FetchError> for SubmitError {
+    fn from(error: FetchError) -> Self {
+        Self::Request(error)
+    }
+}
+
+impl fmt::Display for SubmitError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Refused(refusal) => write!(formatter, "not submitted: {refusal}"),
+            Self::Request(error) => error.fmt(formatter),
+            Self::LogIo { path, .. } => {
+                write!(formatter, "failed to access {}", path.display())
+            }
+            Self::LogFormat { path, line, .. } => {
+                write!(
+                    formatter,
+                    "{}, line {line}: invalid attempt",
+                    path.display()
+                )
+            }
+        }
+    }
+}
+
+impl Error for SubmitError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Refused(_) => None,
+            Self::Request(error) => error.source(),
+            Self::LogIo { source, .. } => Some(source),
+            Self::LogFormat { source, .. } => Some(source),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fetch::YEAR;
+    use crate::registry;
+    use crate::stand_in::StandIn;
+
+    const TOO_HIGH: &str = "<article><p>That's not the right answer; your answer is too high. \
+        If you're stuck, make sure you're using the full input data. Please wait one minute \
+        before trying again. <a href=\"/2024/day/1\">[Return to Day 1]</a></p></article>";
+    const CORRECT: &str = "<article><p>That's the right answer! You are one gold star closer \
+        to finding the Chief Historian.</p></article>";
+
+    fn attempt(answer: &str, verdict: Verdict, submitted_at: u64, wait: Option<u64>) -> Attempt {
+        Attempt {
+            year: YEAR,
+            day: 1,
+            part: 1,
+            answer: answer.to_owned(),
+            verdict,
+            submitted_at,
+            wait,
+        }
+    }
+
+    #[test]
+    fn test_parse_response() {
+        let responses = [
+            CORRECT,
+            TOO_HIGH,
+            "That's not the right answer; your answer is too low. Please wait 5 minutes before trying again.",
+            "That's not the right answer. If you're stuck, ...",
+            "You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 5s left to wait.",
+            "You don't seem to be solving the right level.  Did you already complete it?",
+            "<html>Something else</html>",
+        ]
+        .map(parse_response);
+
+        assert_eq!(
+            responses.map(|response| (response.verdict, response.wait.map(|wait| wait.as_secs()))),
+            [
+                (Verdict::Correct, None),
+                (Verdict::TooHigh, Some(60)),
+                (Verdict::TooLow, Some(300)),
+                (Verdict::Incorrect, None),
+                (Verdict::RateLimited, Some(65)),
+                (Verdict::WrongLevel, None),
+                (Verdict::Unrecognized, None),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_check() {
+        let log = AnswerLog {
+            path: PathBuf::from(ANSWER_LOG),
+            attempts: vec![
+                attempt("100", Verdict::TooHigh, 1_000, Some(60)),
+                attempt("10", Verdict::TooLow, 1_100, Some(60)),
+                attempt("abc", Verdict::Incorrect, 1_200, None),
+            ],
+        };
+        let check = |answer, now| log.check(YEAR, 1, Part::One, answer, now);
+
+        assert_eq!(
+            check("abc", 2_000),
+            Err(Refusal::KnownWrong {
+                verdict: Verdict::Incorrect
+            })
+        );
+        assert_eq!(
+            check("150", 2_000),
+            Err(Refusal::OutOfBounds {
+                bound: "100".to_owned(),
+                verdict: Verdict::TooHigh
+            })
+        );
+        assert_eq!(
+            check("7", 2_000),
+            Err(Refusal::OutOfBounds {
+                bound: "10".to_owned(),
+                verdict: Verdict::TooLow
+            })
+        );
+        assert_eq!(
+            check("50", 1_130),
+            Err(Refusal::Cooldown {
+                remaining: Duration::from_secs(30)
+            })
+        );
+        assert_eq!(check("50", 2_000), Ok(()));
+        assert_eq!(log.check(YEAR, 1, Part::Two, "150", 2_000), Ok(()));
+    }
+
+    #[test]
+    fn test_cooldown_covers_every_puzzle() {
+        let log = AnswerLog {
+            path: PathBuf::from(ANSWER_LOG),
+            attempts: vec![attempt("100", Verdict::RateLimited, 1_000, Some(60))],
+        };
+        let cooldown = Err(Refusal::Cooldown {
+            remaining: Duration::from_secs(30),
+        });
+
+        assert_eq!(log.check(YEAR, 1, Part::Two, "150", 1_030), cooldown);
+        assert_eq!(log.check(YEAR, 2, Part::One, "150", 1_030), cooldown);
+        assert_eq!(log.check(YEAR, 2, Part::One, "150", 1_060), Ok(()));
+    }
+
+    #[test]
+    fn test_submit() {
+        let stand_in = StandIn::serve(200, CORRECT);
+        let directory = tempfile::tempdir().expect("a temporary directory");
+        let log_path = directory.path().join(SUBMISSION_DIR).join(ANSWER_LOG);
+        let client = Client::new(&stand_in.base_url, Some("secret".to_owned()));
+        let log = AnswerLog::load(&log_path).expect("a missing log is empty");
+        let mut submitter = Submitter::new(client, log);
+        let day = registry::find("1").expect("day one is registered");
+
+        let accepted = submitter
+            .submit(YEAR, day, Part::Two, " 31\n")
+            .expect("the answer is submitted");
+        assert_eq!(accepted.verdict, Verdict::Correct);
+        assert_eq!(accepted.answer, "31");
+
+        let resubmitted = submitter.submit(YEAR, day, Part::Two, "31");
+        assert!(matches!(
+            resubmitted,
+            Err(SubmitError::Refused(Refusal::AlreadySolved { .. }))
+        ));
+
+        let requests = stand_in.requests();
+        assert_eq!(
+            requests
+                .iter()
+                .map(|request| (
+                    request.method.as_str(),
+                    request.url.as_str(),
+                    request.body.as_str()
+                ))
+                .collect::<Vec<_>>(),
+            [("POST", "/2024/day/1/answer", "level=2&answer=31")]
+        );
+
+        let reloaded = AnswerLog::load(&log_path).expect("the log is readable");
+        assert_eq!(reloaded, *submitter.log());
+    }
+}
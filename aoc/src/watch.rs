@@ -0,0 +1,451 @@
+//! Rebuilds and reruns a day whenever its sources or input change.
+//!
+//! Every run is a fresh `cargo run` of the runner with JSON output, so edits
+//! to the day are compiled in. The records of each run are compared with the
+//! previous one to show which answers changed and how the timings moved.
+
+use crate::fetch;
+use crate::registry::Day;
+use aoc_common::parallel::{Parallelism, THREADS_VAR};
+use aoc_common::Part;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::env;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+/// Target directory of the watched builds, so they neither wait for nor
+/// replace the binary that is watching.
+const TARGET_DIR: &str = "target/watch";
+
+/// The result of one part in the JSON output of a run.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct Observed {
+    pub part: Option<u8>,
+    pub answer: Option<String>,
+    pub error: Option<String>,
+    pub parse_nanos: Option<u64>,
+    pub solve_nanos: Option<u64>,
+}
+
+/// Size and modification time of every watched file.
+pub type Snapshot = BTreeMap<PathBuf, (u64, Option<SystemTime>)>;
+
+/// Records the files at `paths`, descending into directories.
+///
+/// Files that disappear while they are listed are skipped, they show up as
+/// a change in the next snapshot.
+#[must_use]
+pub fn snapshot(paths: &[PathBuf]) -> Snapshot {
+    let mut snapshot = Snapshot::new();
+    let mut pending = paths.to_vec();
+
+    while let Some(path) = pending.pop() {
+        let Ok(metadata) = fs::metadata(&path) else {
+            continue;
+        };
+        if metadata.is_dir() {
+            if let Ok(entries) = fs::read_dir(&path) {
+                pending.extend(entries.filter_map(Result::ok).map(|entry| entry.path()));
+            }
+        } else {
+            snapshot.insert(path, (metadata.len(), metadata.modified().ok()));
+        }
+    }
+
+    snapshot
+}
+
+/// What to watch and how to run it.
+#[derive(Debug, Clone)]
+pub struct Watch {
+    pub day: &'static Day,
+    pub parts: Vec<Part>,
+    /// Absolute path of the input file to use instead of the day's sample.
+    /// The runs start in the workspace, not where the watch was started.
+    pub input: Option<PathBuf>,
+    /// Build with optimizations, for timings that mean something.
+    pub release: bool,
+    /// How often to look for changes.
+    pub interval: Duration,
+    /// Threads of the runs, passed on through [`THREADS_VAR`].
+    pub parallelism: Parallelism,
+}
+
+impl Watch {
+    /// Watches `day`, resolving `input` against the current directory.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WatchError::Input`] if the input file cannot be found.
+    pub fn new(
+        day: &'static Day,
+        parts: Vec<Part>,
+        input: Option<&Path>,
+        release: bool,
+        interval: Duration,
+        parallelism: Parallelism,
+    ) -> Result<Self, WatchError> {
+        let input = input
+            .map(|path| {
+                fs::canonicalize(path).map_err(|source| WatchError::Input {
+                    path: path.to_owned(),
+                    source,
+                })
+            })
+            .transpose()?;
+
+        Ok(Self {
+            day,
+            parts,
+            input,
+            release,
+            interval,
+            parallelism,
+        })
+    }
+
+    /// The day's crate directory and the input file, if one is given.
+    #[must_use]
+    pub fn paths(&self) -> Vec<PathBuf> {
+        let mut paths = vec![fetch::workspace_dir().join(self.day.directory())];
+        paths.extend(self.input.clone());
+        paths
+    }
+
+    /// Rebuilds the runner and runs the day with it. Compiler output goes to
+    /// standard error as usual.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`WatchError`] if cargo cannot be started, the build fails
+    /// or the run prints something other than its records.
+    pub fn run(&self) -> Result<Vec<Observed>, WatchError> {
+        let workspace = fetch::workspace_dir();
+        let cargo = env::var_os("CARGO").unwrap_or_else(|| "cargo".into());
+
+        let mut command = Command::new(cargo);
+        let threads = self
+            .parallelism
+            .threads()
+            .map_or_else(|| "auto".to_owned(), |threads| threads.to_string());
+        command
+            .current_dir(workspace)
+            .env(THREADS_VAR, threads)
+            .args(["run", "--quiet", "--package", "aoc", "--target-dir"])
+            .arg(workspace.join(TARGET_DIR));
+        if self.release {
+            command.arg("--release");
+        }
+        command.args(["--", "run", self.day.name, "--format", "json"]);
+        if let [part] = self.parts.as_slice() {
+            command.args(["--part", &part.to_string()]);
+        }
+        if let Some(input) = &self.input {
+            command.arg("--input").arg(input);
+        }
+
+        let output = command
+            .stdin(Stdio::null())
+            .stderr(Stdio::inherit())
+            .output()
+            .map_err(WatchError::Spawn)?;
+        // The runner fails if a part fails, but still prints its records
+        match serde_json::from_slice(&output.stdout) {
+            Ok(observed) => Ok(observed),
+            Err(_) if !output.status.success() && output.stdout.is_empty() => {
+                Err(WatchError::Build(output.status.code()))
+            }
+            Err(source) => Err(WatchError::Output(source)),
+        }
+    }
+
+    /// Runs the day now and again after every change, writing what changed
+    /// between runs to `out`. Only returns on errors.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`WatchError`] if cargo cannot be started or writing to
+    /// `out` fails. Failed builds are reported and watched through.
+    pub fn watch(&self, out: &mut impl Write) -> Result<(), WatchError> {
+        let paths = self.paths();
+        let mut previous = None;
+
+        loop {
+            let mut seen = snapshot(&paths);
+            writeln!(out, "Running day {} ({})", self.day.number, self.day.name)?;
+            match self.run() {
+                Ok(observed) => {
+                    write_diff(out, previous.as_deref(), &observed)?;
+                    previous = Some(observed);
+                }
+                Err(WatchError::Spawn(source)) => return Err(WatchError::Spawn(source)),
+                Err(error) => writeln!(out, "{error}")?,
+            }
+            writeln!(
+                out,
+                "Watching {}",
+                paths
+                    .iter()
+                    .map(|path| path.display().to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )?;
+            out.flush()?;
+
+            // Wait for a change, then until the files settle so a save
+            // touching several of them triggers a single run
+            loop {
+                thread::sleep(self.interval);
+                let current = snapshot(&paths);
+                if current != seen {
+                    seen = current;
+                    break;
+                }
+            }
+            loop {
+                thread::sleep(self.interval);
+                let current = snapshot(&paths);
+                if current == seen {
+                    break;
+                }
+                seen = current;
+            }
+        }
+    }
+}
+
+fn describe_part(part: Option<u8>) -> String {
+    part.map_or_else(|| "-".to_owned(), |part| format!("part {part}"))
+}
+
+/// Formats `current` with the change from `previous`, if there is one.
+fn describe_time(current: Option<u64>, previous: Option<u64>) -> String {
+    let Some(current) = current else {
+        return String::new();
+    };
+    let time = Duration::from_nanos(current);
+    match previous.filter(|&previous| previous > 0) {
+        Some(previous) => {
+            let change = (i128::from(current) - i128::from(previous)) * 100 / i128::from(previous);
+            format!("{time:.2?} ({change:+}%)")
+        }
+        None => format!("{time:.2?}"),
+    }
+}
+
+/// Writes one line for parsing and one per part of `current`, marking
+/// answers that differ from `previous`.
+///
+/// # Errors
+///
+/// Returns any error from writing to `out`.
+pub fn write_diff(
+    out: &mut impl Write,
+    previous: Option<&[Observed]>,
+    current: &[Observed],
+) -> io::Result<()> {
+    let parse_nanos =
+        |observed: &[Observed]| observed.iter().find_map(|observed| observed.parse_nanos);
+    let parse = describe_time(parse_nanos(current), previous.and_then(parse_nanos));
+    if !parse.is_empty() {
+        writeln!(out, "  {:<6}  {:<24}  {parse}", "parse", "")?;
+    }
+
+    for observed in current {
+        let before = previous
+            .and_then(|previous| previous.iter().find(|before| before.part == observed.part));
+        let label = describe_part(observed.part);
+
+        let answer = match (&observed.error, &observed.answer) {
+            (Some(error), _) => format!("error: {error}"),
+            (None, Some(answer)) => match before.and_then(|before| before.answer.as_ref()) {
+                Some(old) if old != answer => format!("{old} -> {answer}"),
+                _ => answer.clone(),
+            },
+            (None, None) => String::new(),
+        };
+        let time = describe_time(
+            observed.solve_nanos,
+            before.and_then(|before| before.solve_nanos),
+        );
+
+        let line = format!("  {label:<6}  {answer:<24}  {time}");
+        writeln!(out, "{}", line.trim_end())?;
+    }
+
+    Ok(())
+}
+
+#[derive(Debug)]
+pub enum WatchError {
+    /// The input file could not be found.
+    Input { path: PathBuf, source: io::Error },
+    /// Cargo could not be started.
+    Spawn(io::Error),
+    /// The build failed with this exit code.
+    Build(Option<i32>),
+    /// The run printed something other than its records.
+    Output(serde_json::Error),
+    /// The results could not be written.
+    Write(io::Error),
+}
+
+impl From<io::Error> for WatchError {
+    fn from(source: io::Error) -> Self {
+        Self::Write(source)
+    }
+}
+
+impl fmt::Display for WatchError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Input { path, .. } => write!(formatter, "cannot find {}", path.display()),
+            Self::Spawn(_) => formatter.write_str("failed to start cargo"),
+            Self::Build(Some(code)) => write!(formatter, "build failed with exit code {code}"),
+            Self::Build(None) => formatter.write_str("build was interrupted"),
+            Self::Output(_) => formatter.write_str("unexpected output from the run"),
+            Self::Write(_) => formatter.write_str("failed to write the results"),
+        }
+    }
+}
+
+impl Error for WatchError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Input { source, .. } | Self::Spawn(source) | Self::Write(source) => Some(source),
+            Self::Output(source) => Some(source),
+            Self::Build(_) => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry;
+
+    fn observed(part: u8, answer: &str, solve_nanos: u64) -> Observed {
+        Observed {
+            part: Some(part),
+            answer: Some(answer.to_owned()),
+            error: None,
+            parse_nanos: Some(1_000),
+            solve_nanos: Some(solve_nanos),
+        }
+    }
+
+    #[test]
+    fn test_observed() {
+        let output = r#"[{"day": 1, "name": "one", "part": 1, "answer": "11",
+            "error": null, "parse_nanos": 1000, "solve_nanos": 2000,
+            "input_sha256": "abc"}]"#;
+        let parsed = serde_json::from_str::<Vec<Observed>>(output).ok();
+        assert_eq!(parsed, Some(vec![observed(1, "11", 2_000)]));
+    }
+
+    #[test]
+    fn test_write_diff() {
+        let previous = [observed(1, "11", 2_000), observed(2, "31", 4_000)];
+        let current = [
+            observed(1, "11", 3_000),
+            Observed {
+                error: Some("part 2 panicked: oops".to_owned()),
+                answer: None,
+                solve_nanos: None,
+                ..observed(2, "", 0)
+            },
+        ];
+
+        let mut first = Vec::new();
+        write_diff(&mut first, None, &previous).expect("writing to a vector");
+        assert_eq!(
+            String::from_utf8(first).ok().as_deref(),
+            Some(concat!(
+                "  parse                             1.00µs\n",
+                "  part 1  11                        2.00µs\n",
+                "  part 2  31                        4.00µs\n",
+            ))
+        );
+
+        let mut second = Vec::new();
+        write_diff(&mut second, Some(&previous), &current).expect("writing to a vector");
+        assert_eq!(
+            String::from_utf8(second).ok().as_deref(),
+            Some(concat!(
+                "  parse                             1.00µs (+0%)\n",
+                "  part 1  11                        3.00µs (+50%)\n",
+                "  part 2  error: part 2 panicked: oops\n",
+            ))
+        );
+
+        let changed = [observed(1, "12", 1_000)];
+        let mut third = Vec::new();
+        write_diff(&mut third, Some(&previous), &changed).expect("writing to a vector");
+        assert_eq!(
+            String::from_utf8(third).ok().as_deref(),
+            Some(concat!(
+                "  parse                             1.00µs (+0%)\n",
+                "  part 1  11 -> 12                  1.00µs (-50%)\n",
+            ))
+        );
+    }
+
+    #[test]
+    fn test_snapshot() {
+        let directory = tempfile::tempdir().expect("a temporary directory");
+        let source = directory.path().join("src/lib.rs");
+        fs::create_dir_all(directory.path().join("src")).expect("the directory can be created");
+        fs::write(&source, "fn main() {}").expect("the file can be written");
+        let input = directory.path().join("input.txt");
+        fs::write(&input, "1 2").expect("the file can be written");
+        let paths = [
+            directory.path().join("src"),
+            input.clone(),
+            PathBuf::from("missing"),
+        ];
+
+        let before = snapshot(&paths);
+        assert_eq!(before.keys().collect::<Vec<_>>(), [&input, &source]);
+        assert_eq!(snapshot(&paths), before);
+
+        fs::write(&source, "fn main() { todo!() }").expect("the file can be written");
+        assert_ne!(snapshot(&paths), before);
+    }
+
+    #[test]
+    fn test_input_is_absolute() {
+        let day = registry::find("one").expect("day one is registered");
+        let watch = |input: &str| {
+            Watch::new(
+                day,
+                Part::ALL.to_vec(),
+                Some(Path::new(input)),
+                false,
+                Duration::ZERO,
+                Parallelism::Auto,
+            )
+        };
+
+        // Tests run in the crate directory, the runs in the workspace
+        let input = watch("Cargo.toml")
+            .expect("the manifest exists")
+            .input
+            .expect("an input is given");
+        assert!(input.is_absolute());
+        assert_eq!(
+            Some(input),
+            fs::canonicalize(Path::new(env!("CARGO_MANIFEST_DIR")).join("Cargo.toml")).ok()
+        );
+        assert!(matches!(
+            watch("missing.txt"),
+            Err(WatchError::Input { .. })
+        ));
+    }
+}
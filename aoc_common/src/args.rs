@@ -0,0 +1,94 @@
+//! The command line of a day's binary: the input as the first positional
+//! argument, options written as `--name value` or `--name=value`, and the
+//! [`FLAGS`] that take no value.
+
+use std::env;
+use std::ffi::{OsStr, OsString};
+
+/// Options that are switched on by their name alone.
+pub const FLAGS: &[&str] = &["step"];
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Args {
+    positional: Vec<OsString>,
+    options: Vec<(String, OsString)>,
+}
+
+impl Args {
+    /// Splits `args`, which do not include the program name. An option at
+    /// the end without a value gets an empty one.
+    pub fn parse(args: impl IntoIterator<Item = OsString>) -> Self {
+        let mut parsed = Self::default();
+        let mut args = args.into_iter();
+
+        while let Some(arg) = args.next() {
+            let Some(option) = arg.to_str().and_then(|arg| arg.strip_prefix("--")) else {
+                parsed.positional.push(arg);
+                continue;
+            };
+            let (name, value) = match option.split_once('=') {
+                Some((name, value)) => (name.to_owned(), OsString::from(value)),
+                None if FLAGS.contains(&option) => (option.to_owned(), OsString::new()),
+                None => (option.to_owned(), args.next().unwrap_or_default()),
+            };
+            parsed.options.push((name, value));
+        }
+
+        parsed
+    }
+
+    /// The arguments the process was started with.
+    #[must_use]
+    pub fn from_env() -> Self {
+        Self::parse(env::args_os().skip(1))
+    }
+
+    #[must_use]
+    pub fn positional(&self, index: usize) -> Option<&OsStr> {
+        self.positional.get(index).map(OsString::as_os_str)
+    }
+
+    /// Whether the `--name` flag is given.
+    #[must_use]
+    pub fn flag(&self, name: &str) -> bool {
+        self.options.iter().any(|(option, _)| option == name)
+    }
+
+    /// The value of the last `--name` option.
+    #[must_use]
+    pub fn option(&self, name: &str) -> Option<&OsStr> {
+        self.options
+            .iter()
+            .rev()
+            .find(|(option, _)| option == name)
+            .map(|(_, value)| value.as_os_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Args {
+        Args::parse(args.iter().map(OsString::from))
+    }
+
+    #[test]
+    fn test_parse() {
+        let args = parse(&["--image", "out.png", "input.txt", "--cell-size=4", "-"]);
+        assert_eq!(args.positional(0), Some(OsStr::new("input.txt")));
+        assert_eq!(args.positional(1), Some(OsStr::new("-")));
+        assert_eq!(args.option("image"), Some(OsStr::new("out.png")));
+        assert_eq!(args.option("cell-size"), Some(OsStr::new("4")));
+        assert_eq!(args.option("palette"), None);
+
+        let flagged = parse(&["--step", "input.txt"]);
+        assert!(flagged.flag("step"));
+        assert!(!flagged.flag("image"));
+        assert_eq!(flagged.positional(0), Some(OsStr::new("input.txt")));
+
+        let trailing = parse(&["--image=a.svg", "--image"]);
+        assert_eq!(trailing.positional(0), None);
+        assert_eq!(trailing.option("image"), Some(OsStr::new("")));
+    }
+}
@@ -0,0 +1,121 @@
+//! Synthetic puzzle inputs for stress testing.
+//!
+//! Every day can produce valid inputs of any size from a seed. The same seed
+//! and size always give the same input, on every platform, so a slow or
+//! failing input can be reproduced from the two numbers alone.
+
+use crate::geometry::Point;
+use crate::solution::Solution;
+use std::ops::RangeInclusive;
+
+/// A small deterministic random number generator (`SplitMix64`).
+///
+/// Good enough to shape puzzle inputs, but not for anything that needs
+/// unpredictable numbers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    #[must_use]
+    pub const fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut mixed = self.state;
+        mixed = (mixed ^ (mixed >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        mixed = (mixed ^ (mixed >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        mixed ^ (mixed >> 31)
+    }
+
+    /// A number below `bound`, or `0` if `bound` is `0`.
+    pub fn below(&mut self, bound: usize) -> usize {
+        let bound = u64::try_from(bound).unwrap_or(u64::MAX);
+        if bound == 0 {
+            return 0;
+        }
+        // The slight bias towards small numbers doesn't matter for inputs
+        usize::try_from(self.next_u64() % bound).unwrap_or_default()
+    }
+
+    /// A number in `range`, or its start if the range is empty.
+    pub fn range(&mut self, range: RangeInclusive<i64>) -> i64 {
+        let (start, end) = range.into_inner();
+        if end <= start {
+            return start;
+        }
+        let span = end.abs_diff(start).saturating_add(1);
+        start.wrapping_add_unsigned(self.next_u64() % span)
+    }
+
+    /// A point within a `width` x `height` grid.
+    pub fn point(&mut self, width: usize, height: usize) -> Point {
+        Point::new(self.below(width), self.below(height))
+    }
+
+    /// `true` with a chance of `percent` in a hundred.
+    pub fn chance(&mut self, percent: u8) -> bool {
+        self.below(100) < usize::from(percent)
+    }
+
+    /// A random element of `items`, or `None` if there are none.
+    pub fn pick<'items, T>(&mut self, items: &'items [T]) -> Option<&'items T> {
+        items.get(self.below(items.len()))
+    }
+
+    /// Puts `items` in a random order.
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i + 1));
+        }
+    }
+}
+
+/// A day that can make up its own puzzle inputs.
+pub trait Generate: Solution {
+    /// What the size of a generated input counts, such as `reports`.
+    const SIZE: &'static str;
+    /// Size of the real puzzle input.
+    const DEFAULT_SIZE: usize;
+
+    /// A valid puzzle input of roughly `size`, as counted by [`Self::SIZE`].
+    fn generate(rng: &mut Rng, size: usize) -> String;
+
+    /// The input for `seed` and `size`.
+    #[must_use]
+    fn generate_seeded(seed: u64, size: usize) -> String {
+        Self::generate(&mut Rng::new(seed), size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rng() {
+        assert_eq!(Rng::new(0).next_u64(), 0xe220_a839_7b1d_cdaf);
+        assert_eq!(Rng::new(7), Rng::new(7));
+
+        let mut rng = Rng::new(42);
+        for _ in 0..1000 {
+            assert!(rng.below(10) < 10, "below stays below its bound");
+            assert!(
+                (-3..=3).contains(&rng.range(-3..=3)),
+                "range stays within its bounds"
+            );
+        }
+        assert_eq!(rng.below(0), 0);
+        assert_eq!(rng.range(5..=5), 5);
+        assert_eq!(rng.pick::<u8>(&[]), None);
+
+        let mut items = (0..20).collect::<Vec<_>>();
+        rng.shuffle(&mut items);
+        assert_ne!(items, (0..20).collect::<Vec<_>>());
+        items.sort_unstable();
+        assert_eq!(items, (0..20).collect::<Vec<_>>());
+    }
+}
@@ -0,0 +1,397 @@
+//! Points, vectors and directions on the integer plane. As in
+//! [`Grid`](crate::Grid), `x` grows to the right and `y` grows downwards.
+
+use std::num::TryFromIntError;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub};
+
+/// A position with unsigned coordinates, e.g. a tile of a grid.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point {
+    pub x: usize,
+    pub y: usize,
+}
+
+/// A position that may lie left of or above the origin.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SignedPoint {
+    pub x: isize,
+    pub y: isize,
+}
+
+/// The displacement between two points.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Vector {
+    pub x: isize,
+    pub y: isize,
+}
+
+impl Point {
+    pub const ORIGIN: Self = Self::new(0, 0);
+
+    #[must_use]
+    pub const fn new(x: usize, y: usize) -> Self {
+        Self { x, y }
+    }
+
+    /// Moves by `vector`, returning `None` if a coordinate would become
+    /// negative or overflow.
+    #[must_use]
+    pub fn checked_add(self, vector: Vector) -> Option<Self> {
+        Some(Self::new(
+            self.x.checked_add_signed(vector.x)?,
+            self.y.checked_add_signed(vector.y)?,
+        ))
+    }
+
+    /// Moves against `vector`, returning `None` if a coordinate would become
+    /// negative or overflow.
+    #[must_use]
+    pub fn checked_sub(self, vector: Vector) -> Option<Self> {
+        self.checked_add(-vector)
+    }
+
+    /// Moves by `vector` on a `width` x `height` torus, wrapping around the
+    /// edges.
+    #[must_use]
+    pub fn wrapping_add(self, vector: Vector, width: usize, height: usize) -> Self {
+        Self::new(
+            wrap(self.x, vector.x, width),
+            wrap(self.y, vector.y, height),
+        )
+    }
+
+    /// The vector leading from `self` to `other`.
+    #[must_use]
+    pub const fn vector_to(self, other: Self) -> Vector {
+        Vector::new(
+            other.x.wrapping_sub(self.x).cast_signed(),
+            other.y.wrapping_sub(self.y).cast_signed(),
+        )
+    }
+
+    /// Moves one step in `direction`, returning `None` when leaving the
+    /// quadrant of non-negative coordinates.
+    #[must_use]
+    pub fn step(self, direction: impl Into<Direction8>) -> Option<Self> {
+        self.checked_add(direction.into().vector())
+    }
+
+    #[must_use]
+    pub const fn manhattan_distance(self, other: Self) -> usize {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+
+    #[must_use]
+    pub fn chebyshev_distance(self, other: Self) -> usize {
+        self.x.abs_diff(other.x).max(self.y.abs_diff(other.y))
+    }
+}
+
+fn wrap(coordinate: usize, delta: isize, size: usize) -> usize {
+    let offset = delta.unsigned_abs() % size;
+    let coordinate = coordinate % size;
+    if delta < 0 {
+        (coordinate + size - offset) % size
+    } else {
+        (coordinate + offset) % size
+    }
+}
+
+impl SignedPoint {
+    pub const ORIGIN: Self = Self::new(0, 0);
+
+    #[must_use]
+    pub const fn new(x: isize, y: isize) -> Self {
+        Self { x, y }
+    }
+
+    #[must_use]
+    pub const fn vector_to(self, other: Self) -> Vector {
+        Vector::new(other.x - self.x, other.y - self.y)
+    }
+
+    #[must_use]
+    pub const fn manhattan_distance(self, other: Self) -> usize {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+
+    #[must_use]
+    pub fn chebyshev_distance(self, other: Self) -> usize {
+        self.x.abs_diff(other.x).max(self.y.abs_diff(other.y))
+    }
+}
+
+impl Vector {
+    pub const ZERO: Self = Self::new(0, 0);
+
+    #[must_use]
+    pub const fn new(x: isize, y: isize) -> Self {
+        Self { x, y }
+    }
+
+    #[must_use]
+    pub const fn manhattan_length(self) -> usize {
+        self.x.unsigned_abs() + self.y.unsigned_abs()
+    }
+
+    #[must_use]
+    pub fn chebyshev_length(self) -> usize {
+        self.x.unsigned_abs().max(self.y.unsigned_abs())
+    }
+}
+
+impl From<Point> for SignedPoint {
+    fn from(point: Point) -> Self {
+        Self::new(point.x.cast_signed(), point.y.cast_signed())
+    }
+}
+
+impl TryFrom<SignedPoint> for Point {
+    type Error = TryFromIntError;
+
+    fn try_from(point: SignedPoint) -> Result<Self, Self::Error> {
+        Ok(Self::new(point.x.try_into()?, point.y.try_into()?))
+    }
+}
+
+impl Add<Vector> for SignedPoint {
+    type Output = Self;
+
+    fn add(self, vector: Vector) -> Self {
+        Self::new(self.x + vector.x, self.y + vector.y)
+    }
+}
+
+impl AddAssign<Vector> for SignedPoint {
+    fn add_assign(&mut self, vector: Vector) {
+        *self = *self + vector;
+    }
+}
+
+impl Sub<Vector> for SignedPoint {
+    type Output = Self;
+
+    fn sub(self, vector: Vector) -> Self {
+        self + -vector
+    }
+}
+
+impl Add for Vector {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl Sub for Vector {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        self + -other
+    }
+}
+
+impl Neg for Vector {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::new(-self.x, -self.y)
+    }
+}
+
+impl Mul<isize> for Vector {
+    type Output = Self;
+
+    fn mul(self, factor: isize) -> Self {
+        Self::new(self.x * factor, self.y * factor)
+    }
+}
+
+/// One of the four orthogonal directions, listed clockwise from `Up`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    pub const ALL: [Self; 4] = [Self::Up, Self::Right, Self::Down, Self::Left];
+
+    #[must_use]
+    pub const fn turn_right(self) -> Self {
+        match self {
+            Self::Up => Self::Right,
+            Self::Right => Self::Down,
+            Self::Down => Self::Left,
+            Self::Left => Self::Up,
+        }
+    }
+
+    #[must_use]
+    pub const fn turn_left(self) -> Self {
+        self.turn_right().reverse()
+    }
+
+    #[must_use]
+    pub const fn reverse(self) -> Self {
+        self.turn_right().turn_right()
+    }
+
+    #[must_use]
+    pub const fn vector(self) -> Vector {
+        match self {
+            Self::Up => Vector::new(0, -1),
+            Self::Right => Vector::new(1, 0),
+            Self::Down => Vector::new(0, 1),
+            Self::Left => Vector::new(-1, 0),
+        }
+    }
+}
+
+/// One of the four orthogonal or four diagonal directions, listed clockwise
+/// from `Up`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction8 {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Direction8 {
+    pub const ALL: [Self; 8] = [
+        Self::Up,
+        Self::UpRight,
+        Self::Right,
+        Self::DownRight,
+        Self::Down,
+        Self::DownLeft,
+        Self::Left,
+        Self::UpLeft,
+    ];
+
+    /// Turns clockwise by 45 degrees.
+    #[must_use]
+    pub const fn turn_right(self) -> Self {
+        match self {
+            Self::Up => Self::UpRight,
+            Self::UpRight => Self::Right,
+            Self::Right => Self::DownRight,
+            Self::DownRight => Self::Down,
+            Self::Down => Self::DownLeft,
+            Self::DownLeft => Self::Left,
+            Self::Left => Self::UpLeft,
+            Self::UpLeft => Self::Up,
+        }
+    }
+
+    /// Turns counter-clockwise by 45 degrees.
+    #[must_use]
+    pub const fn turn_left(self) -> Self {
+        self.reverse().turn_right().turn_right().turn_right()
+    }
+
+    #[must_use]
+    pub const fn reverse(self) -> Self {
+        self.turn_right().turn_right().turn_right().turn_right()
+    }
+
+    #[must_use]
+    pub const fn is_diagonal(self) -> bool {
+        matches!(
+            self,
+            Self::UpRight | Self::DownRight | Self::DownLeft | Self::UpLeft
+        )
+    }
+
+    #[must_use]
+    pub const fn vector(self) -> Vector {
+        match self {
+            Self::Up => Vector::new(0, -1),
+            Self::UpRight => Vector::new(1, -1),
+            Self::Right => Vector::new(1, 0),
+            Self::DownRight => Vector::new(1, 1),
+            Self::Down => Vector::new(0, 1),
+            Self::DownLeft => Vector::new(-1, 1),
+            Self::Left => Vector::new(-1, 0),
+            Self::UpLeft => Vector::new(-1, -1),
+        }
+    }
+}
+
+impl From<Direction> for Direction8 {
+    fn from(direction: Direction) -> Self {
+        match direction {
+            Direction::Up => Self::Up,
+            Direction::Right => Self::Right,
+            Direction::Down => Self::Down,
+            Direction::Left => Self::Left,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_point_arithmetic() {
+        let point = Point::new(2, 3);
+        assert_eq!(
+            point.checked_add(Vector::new(-2, 1)),
+            Some(Point::new(0, 4))
+        );
+        assert_eq!(point.checked_add(Vector::new(-3, 0)), None);
+        assert_eq!(point.checked_sub(Vector::new(2, 3)), Some(Point::ORIGIN));
+        assert_eq!(point.vector_to(Point::new(0, 5)), Vector::new(-2, 2));
+        assert_eq!(point.step(Direction::Up), Some(Point::new(2, 2)));
+        assert_eq!(Point::ORIGIN.step(Direction8::UpLeft), None);
+    }
+
+    #[test]
+    fn test_wrapping_add() {
+        let point = Point::new(2, 4);
+        assert_eq!(
+            point.wrapping_add(Vector::new(-3, 3), 11, 7),
+            Point::new(10, 0)
+        );
+        assert_eq!(
+            point.wrapping_add(Vector::new(-25, 14), 11, 7),
+            Point::new(10, 4)
+        );
+    }
+
+    #[test]
+    fn test_distances() {
+        let start = Point::new(1, 1);
+        let end = Point::new(4, 3);
+        assert_eq!(start.manhattan_distance(end), 5);
+        assert_eq!(start.chebyshev_distance(end), 3);
+        let signed = SignedPoint::new(-1, 2);
+        assert_eq!(signed.manhattan_distance(SignedPoint::ORIGIN), 3);
+        assert_eq!(signed.vector_to(SignedPoint::ORIGIN).chebyshev_length(), 2);
+    }
+
+    #[test]
+    fn test_directions() {
+        for direction in Direction::ALL {
+            assert_eq!(direction.turn_right().turn_left(), direction);
+            assert_eq!(direction.reverse().vector(), -direction.vector());
+        }
+        for direction in Direction8::ALL {
+            assert_eq!(direction.turn_right().turn_left(), direction);
+            assert_eq!(direction.reverse().vector(), -direction.vector());
+            assert_eq!(
+                direction.is_diagonal(),
+                direction.vector().manhattan_length() == 2
+            );
+        }
+    }
+}
@@ -0,0 +1,381 @@
+//! A rectangular grid of tiles addressed by [`Point`]s, where `x` is the column
+//! and `y` the row, both counted from the top-left corner.
+
+use crate::geometry::{Direction, Direction8, Point, SignedPoint, Vector};
+use std::error::Error;
+use std::fmt;
+use std::iter;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GridError {
+    /// A row does not have the same length as the first row.
+    Ragged {
+        row: usize,
+        expected: usize,
+        found: usize,
+    },
+    /// The tile mapping rejected a character.
+    InvalidTile {
+        row: usize,
+        column: usize,
+        tile: char,
+    },
+}
+
+impl fmt::Display for GridError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Ragged {
+                row,
+                expected,
+                found,
+            } => write!(
+                formatter,
+                "line {}: expected {expected} tiles but found {found}",
+                row + 1
+            ),
+            Self::InvalidTile { row, column, tile } => write!(
+                formatter,
+                "line {}, column {}: invalid tile {tile:?}",
+                row + 1,
+                column + 1
+            ),
+        }
+    }
+}
+
+impl Error for GridError {}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Creates a `width` x `height` grid with every tile set to `value`.
+    #[must_use]
+    pub fn new(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    /// Builds a grid from its rows.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GridError::Ragged`] if the rows differ in length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Self, GridError> {
+        let width = rows.first().map_or(0, Vec::len);
+        let height = rows.len();
+        let mut cells = Vec::with_capacity(width * height);
+
+        for (row_index, row) in rows.into_iter().enumerate() {
+            if row.len() != width {
+                return Err(GridError::Ragged {
+                    row: row_index,
+                    expected: width,
+                    found: row.len(),
+                });
+            }
+            cells.extend(row);
+        }
+
+        Ok(Self {
+            width,
+            height,
+            cells,
+        })
+    }
+
+    /// Parses one row per line, turning every character into a tile with
+    /// `tile`. Returning `None` from the hook rejects the character.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`GridError`] if a character is rejected or the lines differ
+    /// in length.
+    pub fn parse_with<F>(input: &str, mut tile: F) -> Result<Self, GridError>
+    where
+        F: FnMut(char) -> Option<T>,
+    {
+        let rows = input
+            .lines()
+            .enumerate()
+            .map(|(row, line)| {
+                line.chars()
+                    .enumerate()
+                    .map(|(column, char)| {
+                        tile(char).ok_or(GridError::InvalidTile {
+                            row,
+                            column,
+                            tile: char,
+                        })
+                    })
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Self::from_rows(rows)
+    }
+
+    /// Parses one row per line, converting every character with
+    /// `T::try_from`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`GridError`] if a conversion fails or the lines differ in
+    /// length.
+    pub fn parse(input: &str) -> Result<Self, GridError>
+    where
+        T: TryFrom<char>,
+    {
+        Self::parse_with(input, |char| T::try_from(char).ok())
+    }
+
+    #[must_use]
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    #[must_use]
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    #[must_use]
+    pub fn contains(&self, point: Point) -> bool {
+        point.x < self.width && point.y < self.height
+    }
+
+    /// Converts a signed point into a position inside the grid.
+    #[must_use]
+    pub fn checked_position(&self, point: SignedPoint) -> Option<Point> {
+        Point::try_from(point)
+            .ok()
+            .filter(|&point| self.contains(point))
+    }
+
+    /// Moves `point` by `vector`, staying inside the grid.
+    #[must_use]
+    pub fn offset(&self, point: Point, vector: Vector) -> Option<Point> {
+        point
+            .checked_add(vector)
+            .filter(|&point| self.contains(point))
+    }
+
+    /// Moves `point` one step in `direction`, staying inside the grid.
+    #[must_use]
+    pub fn step(&self, point: Point, direction: impl Into<Direction8>) -> Option<Point> {
+        self.offset(point, direction.into().vector())
+    }
+
+    fn index(&self, point: Point) -> Option<usize> {
+        self.contains(point).then(|| point.y * self.width + point.x)
+    }
+
+    #[must_use]
+    pub fn get(&self, point: Point) -> Option<&T> {
+        self.cells.get(self.index(point)?)
+    }
+
+    pub fn get_mut(&mut self, point: Point) -> Option<&mut T> {
+        let index = self.index(point)?;
+        self.cells.get_mut(index)
+    }
+
+    #[must_use]
+    pub fn row(&self, y: usize) -> Option<&[T]> {
+        if y >= self.height {
+            return None;
+        }
+        let start = y * self.width;
+        self.cells.get(start..start + self.width)
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> + '_ {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> + '_ {
+        self.line(Point::new(x, 0), Direction::Down.vector())
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T> + '_> + '_ {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// Every diagonal running from top-left to bottom-right, starting along
+    /// the top row and then down the left column.
+    pub fn diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = &T> + '_> + '_ {
+        let top = (0..self.width).map(|x| Point::new(x, 0));
+        let left = (1..self.height).map(|y| Point::new(0, y));
+        top.chain(left)
+            .map(|start| self.line(start, Direction8::DownRight.vector()))
+    }
+
+    /// Every diagonal running from top-right to bottom-left, starting along
+    /// the top row and then down the right column.
+    pub fn anti_diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = &T> + '_> + '_ {
+        let top = (0..self.width).map(|x| Point::new(x, 0));
+        let right = (1..self.height).map(|y| Point::new(self.width.saturating_sub(1), y));
+        top.chain(right)
+            .map(|start| self.line(start, Direction8::DownLeft.vector()))
+    }
+
+    /// Tiles from `start` moving by `step` until leaving the grid.
+    pub fn line(&self, start: Point, step: Vector) -> impl Iterator<Item = &T> + '_ {
+        self.ray(start, step).filter_map(|point| self.get(point))
+    }
+
+    /// Positions from `start` moving by `step` until leaving the grid.
+    pub fn ray(&self, start: Point, step: Vector) -> impl Iterator<Item = Point> + '_ {
+        let first = self.contains(start).then_some(start);
+        iter::successors(first, move |&point| self.offset(point, step))
+    }
+
+    /// All positions in row-major order.
+    pub fn positions(&self) -> impl Iterator<Item = Point> + '_ {
+        (0..self.height).flat_map(move |y| (0..self.width).map(move |x| Point::new(x, y)))
+    }
+
+    /// All tiles together with their position in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> + '_ {
+        self.positions().zip(&self.cells)
+    }
+
+    /// Position of the first tile matching `predicate` in row-major order.
+    pub fn find<P>(&self, mut predicate: P) -> Option<Point>
+    where
+        P: FnMut(&T) -> bool,
+    {
+        self.iter()
+            .find_map(|(point, tile)| predicate(tile).then_some(point))
+    }
+
+    /// The up to four orthogonal neighbours of `point` inside the grid.
+    pub fn neighbours4(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+        Direction::ALL
+            .into_iter()
+            .filter_map(move |direction| self.step(point, direction))
+    }
+
+    /// The up to eight orthogonal and diagonal neighbours of `point` inside
+    /// the grid.
+    pub fn neighbours8(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+        Direction8::ALL
+            .into_iter()
+            .filter_map(move |direction| self.step(point, direction))
+    }
+
+    /// Creates a grid of the same size by mapping every tile.
+    #[must_use]
+    pub fn map<U, F>(&self, tile: F) -> Grid<U>
+    where
+        F: FnMut(&T) -> U,
+    {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(tile).collect(),
+        }
+    }
+}
+
+impl<T> TryFrom<Vec<Vec<T>>> for Grid<T> {
+    type Error = GridError;
+
+    fn try_from(rows: Vec<Vec<T>>) -> Result<Self, Self::Error> {
+        Self::from_rows(rows)
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            for tile in row {
+                write!(formatter, "{tile}")?;
+            }
+            writeln!(formatter)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "abc\ndef\n";
+
+    #[test]
+    fn test_parse() {
+        let grid = Grid::<char>::parse(SAMPLE).expect("rectangular grid");
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid.get(Point::new(2, 1)), Some(&'f'));
+        assert_eq!(grid.get(Point::new(3, 0)), None);
+        assert_eq!(grid.get(Point::new(0, 2)), None);
+        assert_eq!(
+            grid.checked_position(SignedPoint::new(1, 1)),
+            Some(Point::new(1, 1))
+        );
+        assert_eq!(grid.checked_position(SignedPoint::new(-1, 1)), None);
+
+        assert_eq!(
+            Grid::<char>::parse("ab\nc"),
+            Err(GridError::Ragged {
+                row: 1,
+                expected: 2,
+                found: 1
+            })
+        );
+        assert_eq!(
+            Grid::parse_with("12\n3x", |char| char.to_digit(10)),
+            Err(GridError::InvalidTile {
+                row: 1,
+                column: 1,
+                tile: 'x'
+            })
+        );
+    }
+
+    #[test]
+    fn test_lines() {
+        let grid = Grid::<char>::parse(SAMPLE).expect("rectangular grid");
+        let rows = grid.rows().map(String::from_iter).collect::<Vec<_>>();
+        assert_eq!(rows, ["abc", "def"]);
+        let columns = grid.columns().map(String::from_iter).collect::<Vec<_>>();
+        assert_eq!(columns, ["ad", "be", "cf"]);
+        let diagonals = grid.diagonals().map(String::from_iter).collect::<Vec<_>>();
+        assert_eq!(diagonals, ["ae", "bf", "c", "d"]);
+        let anti_diagonals = grid
+            .anti_diagonals()
+            .map(String::from_iter)
+            .collect::<Vec<_>>();
+        assert_eq!(anti_diagonals, ["a", "bd", "ce", "f"]);
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let grid = Grid::new(3, 3, 0_u8);
+        assert_eq!(
+            grid.neighbours4(Point::ORIGIN).collect::<Vec<_>>(),
+            [Point::new(1, 0), Point::new(0, 1)]
+        );
+        assert_eq!(grid.neighbours4(Point::new(1, 1)).count(), 4);
+        assert_eq!(grid.neighbours8(Point::new(1, 1)).count(), 8);
+        assert_eq!(grid.neighbours8(Point::new(2, 2)).count(), 3);
+    }
+}
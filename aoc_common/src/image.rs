@@ -0,0 +1,455 @@
+//! Saving a [`Canvas`] as a PNG or PPM raster image or an SVG vector image.
+//!
+//! Every cell becomes a square filled with its background color, or its
+//! foreground color if it has no background. Uncolored cells are left blank
+//! if their glyph is `.` or whitespace and use the foreground otherwise.
+//!
+//! Days offer the export through the `--image <path>` option, with
+//! `--cell-size <pixels>` and `--palette <dark|light>` to adjust it. The
+//! format follows the file extension.
+
+use crate::args::Args;
+use crate::render::{Canvas, Cell, Color};
+use std::error::Error;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+/// A color as red, green and blue.
+pub type Rgb = [u8; 3];
+
+/// The colors an image is drawn with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Palette {
+    pub background: Rgb,
+    /// Color of uncolored glyphs.
+    pub foreground: Rgb,
+    /// The colors in the order of [`Color::ALL`].
+    pub colors: [Rgb; 16],
+}
+
+impl Palette {
+    /// Bright colors on a dark background, like most terminals.
+    pub const DARK: Self = Self {
+        background: [24, 24, 32],
+        foreground: [204, 204, 204],
+        colors: [
+            [0, 0, 0],
+            [205, 49, 49],
+            [13, 188, 121],
+            [229, 229, 16],
+            [36, 114, 200],
+            [188, 63, 188],
+            [17, 168, 205],
+            [229, 229, 229],
+            [102, 102, 102],
+            [241, 76, 76],
+            [35, 209, 139],
+            [245, 245, 67],
+            [59, 142, 234],
+            [214, 112, 214],
+            [41, 184, 219],
+            [255, 255, 255],
+        ],
+    };
+
+    /// Darker colors on white, for printing.
+    pub const LIGHT: Self = Self {
+        background: [255, 255, 255],
+        foreground: [40, 40, 40],
+        colors: [
+            [0, 0, 0],
+            [180, 30, 30],
+            [20, 140, 60],
+            [170, 130, 0],
+            [30, 80, 180],
+            [150, 40, 150],
+            [0, 130, 150],
+            [120, 120, 120],
+            [80, 80, 80],
+            [220, 60, 60],
+            [40, 170, 90],
+            [200, 160, 20],
+            [60, 110, 220],
+            [190, 80, 190],
+            [30, 160, 180],
+            [160, 160, 160],
+        ],
+    };
+
+    /// The palette called `name`, `dark` or `light`.
+    #[must_use]
+    pub fn named(name: &str) -> Option<Self> {
+        match name {
+            "dark" => Some(Self::DARK),
+            "light" => Some(Self::LIGHT),
+            _ => None,
+        }
+    }
+
+    #[must_use]
+    pub fn color(&self, color: Color) -> Rgb {
+        Color::ALL
+            .iter()
+            .zip(self.colors)
+            .find_map(|(&candidate, rgb)| (candidate == color).then_some(rgb))
+            .unwrap_or(self.foreground)
+    }
+
+    /// The color filling `cell`.
+    #[must_use]
+    pub fn fill(&self, cell: &Cell) -> Rgb {
+        match cell.style.background.or(cell.style.foreground) {
+            Some(color) => self.color(color),
+            None if cell.glyph == '.' || cell.glyph.is_whitespace() => self.background,
+            None => self.foreground,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageFormat {
+    Png,
+    Ppm,
+    Svg,
+}
+
+impl ImageFormat {
+    /// The format matching the extension of `path`.
+    #[must_use]
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+        match extension.as_str() {
+            "png" => Some(Self::Png),
+            "ppm" => Some(Self::Ppm),
+            "svg" => Some(Self::Svg),
+            _ => None,
+        }
+    }
+}
+
+/// Where and how to save an image.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Export {
+    pub path: PathBuf,
+    pub format: ImageFormat,
+    /// Width and height of a cell in pixels.
+    pub cell_size: usize,
+    pub palette: Palette,
+}
+
+impl Export {
+    pub const DEFAULT_CELL_SIZE: usize = 8;
+
+    /// Saves to `path` in the format its extension names, with the default
+    /// cell size and the dark palette.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ImageError::UnknownFormat`] for other extensions.
+    pub fn new(path: impl Into<PathBuf>) -> Result<Self, ImageError> {
+        let path = path.into();
+        let format =
+            ImageFormat::from_path(&path).ok_or_else(|| ImageError::UnknownFormat(path.clone()))?;
+
+        Ok(Self {
+            path,
+            format,
+            cell_size: Self::DEFAULT_CELL_SIZE,
+            palette: Palette::DARK,
+        })
+    }
+
+    /// The export requested with `--image`, if any, adjusted by
+    /// `--cell-size` and `--palette`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`ImageError`] if an option has an invalid value.
+    pub fn from_args(args: &Args) -> Result<Option<Self>, ImageError> {
+        let Some(path) = args.option("image") else {
+            return Ok(None);
+        };
+        let mut export = Self::new(path)?;
+
+        let invalid = |name: &str, value: &str| ImageError::InvalidOption {
+            name: name.to_owned(),
+            value: value.to_owned(),
+        };
+        if let Some(value) = args.option("cell-size") {
+            let value = value.to_string_lossy();
+            export.cell_size = value
+                .parse()
+                .ok()
+                .filter(|&size| size > 0)
+                .ok_or_else(|| invalid("cell-size", &value))?;
+        }
+        if let Some(value) = args.option("palette") {
+            let value = value.to_string_lossy();
+            export.palette = Palette::named(&value).ok_or_else(|| invalid("palette", &value))?;
+        }
+
+        Ok(Some(export))
+    }
+
+    /// Writes `canvas` to the file.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ImageError::Io`] if the file cannot be written.
+    pub fn save(&self, canvas: &Canvas) -> Result<(), ImageError> {
+        let io_error = |source| ImageError::Io {
+            path: self.path.clone(),
+            source,
+        };
+        let mut out = BufWriter::new(File::create(&self.path).map_err(io_error)?);
+        self.write(&mut out, canvas).map_err(io_error)?;
+        out.flush().map_err(io_error)
+    }
+
+    /// Writes `canvas` to `out` in the export's format.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error from writing to `out`.
+    pub fn write(&self, out: &mut impl Write, canvas: &Canvas) -> io::Result<()> {
+        match self.format {
+            ImageFormat::Png => self.write_png(out, canvas),
+            ImageFormat::Ppm => self.write_ppm(out, canvas),
+            ImageFormat::Svg => self.write_svg(out, canvas),
+        }
+    }
+
+    /// Width and height of the image in pixels.
+    fn dimensions(&self, canvas: &Canvas) -> (usize, usize) {
+        (
+            canvas.width() * self.cell_size,
+            canvas.height() * self.cell_size,
+        )
+    }
+
+    /// The RGB bytes of every pixel, row by row.
+    fn pixels(&self, canvas: &Canvas) -> Vec<u8> {
+        let (width, height) = self.dimensions(canvas);
+        let mut pixels = Vec::with_capacity(width * height * 3);
+
+        for y in 0..canvas.height() {
+            let row = canvas
+                .row(y)
+                .iter()
+                .flat_map(|cell| {
+                    let fill = self.palette.fill(cell);
+                    (0..self.cell_size).flat_map(move |_| fill)
+                })
+                .collect::<Vec<_>>();
+            for _ in 0..self.cell_size {
+                pixels.extend_from_slice(&row);
+            }
+        }
+
+        pixels
+    }
+
+    fn write_ppm(&self, out: &mut impl Write, canvas: &Canvas) -> io::Result<()> {
+        let (width, height) = self.dimensions(canvas);
+        write!(out, "P6\n{width} {height}\n255\n")?;
+        out.write_all(&self.pixels(canvas))
+    }
+
+    fn write_png(&self, out: &mut impl Write, canvas: &Canvas) -> io::Result<()> {
+        let (width, height) = self.dimensions(canvas);
+        let mut encoder = png::Encoder::new(
+            out,
+            u32::try_from(width).map_err(io::Error::other)?,
+            u32::try_from(height).map_err(io::Error::other)?,
+        );
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(&self.pixels(canvas))?;
+        writer.finish()?;
+        Ok(())
+    }
+
+    /// One rectangle per run of equally filled cells in a row, on top of
+    /// the background.
+    fn write_svg(&self, out: &mut impl Write, canvas: &Canvas) -> io::Result<()> {
+        let (width, height) = self.dimensions(canvas);
+        let size = self.cell_size;
+        writeln!(
+            out,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}" shape-rendering="crispEdges">"#
+        )?;
+        writeln!(
+            out,
+            r#"<rect width="{width}" height="{height}" fill="{}"/>"#,
+            hex(self.palette.background)
+        )?;
+
+        for y in 0..canvas.height() {
+            let fills = canvas.row(y).iter().map(|cell| self.palette.fill(cell));
+            let mut runs = Vec::<(usize, usize, Rgb)>::new();
+            for (x, fill) in fills.enumerate() {
+                match runs.last_mut() {
+                    Some((_, length, last)) if *last == fill => *length += 1,
+                    _ => runs.push((x, 1, fill)),
+                }
+            }
+
+            for (x, length, fill) in runs {
+                if fill == self.palette.background {
+                    continue;
+                }
+                writeln!(
+                    out,
+                    r#"<rect x="{}" y="{}" width="{}" height="{size}" fill="{}"/>"#,
+                    x * size,
+                    y * size,
+                    length * size,
+                    hex(fill)
+                )?;
+            }
+        }
+
+        writeln!(out, "</svg>")
+    }
+}
+
+fn hex([red, green, blue]: Rgb) -> String {
+    format!("#{red:02x}{green:02x}{blue:02x}")
+}
+
+#[derive(Debug)]
+pub enum ImageError {
+    /// The file extension names no supported format.
+    UnknownFormat(PathBuf),
+    /// A command line option has an invalid value.
+    InvalidOption {
+        name: String,
+        value: String,
+    },
+    Io {
+        path: PathBuf,
+        source: io::Error,
+    },
+}
+
+impl fmt::Display for ImageError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownFormat(path) => write!(
+                formatter,
+                "cannot tell the image format of {}, expected .png, .ppm or .svg",
+                path.display()
+            ),
+            Self::InvalidOption { name, value } => {
+                write!(formatter, "invalid value {value:?} for --{name}")
+            }
+            Self::Io { path, .. } => write!(formatter, "failed to write {}", path.display()),
+        }
+    }
+}
+
+impl Error for ImageError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Io { source, .. } => Some(source),
+            Self::UnknownFormat(_) | Self::InvalidOption { .. } => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometry::Point;
+    use crate::render::Style;
+    use std::ffi::OsString;
+
+    fn canvas() -> Canvas {
+        let mut canvas = Canvas::new(2, 1, Cell::plain('.'));
+        canvas.set(Point::new(1, 0), Cell::new('#', Style::fg(Color::Red)));
+        canvas
+    }
+
+    fn export(path: &str, cell_size: usize) -> Export {
+        Export {
+            cell_size,
+            ..Export::new(path).expect("a known format")
+        }
+    }
+
+    #[test]
+    fn test_from_args() {
+        let args = |args: &[&str]| Args::parse(args.iter().map(OsString::from));
+
+        assert_eq!(Export::from_args(&args(&["input.txt"])).ok(), Some(None));
+        let export = Export::from_args(&args(&[
+            "--image",
+            "out.SVG",
+            "--cell-size=3",
+            "--palette",
+            "light",
+        ]))
+        .ok()
+        .flatten();
+        assert_eq!(
+            export.map(|export| (export.format, export.cell_size, export.palette)),
+            Some((ImageFormat::Svg, 3, Palette::LIGHT))
+        );
+
+        for invalid in [
+            &["--image", "out.gif"][..],
+            &["--image", "out.png", "--cell-size", "0"],
+            &["--image", "out.png", "--palette", "neon"],
+        ] {
+            assert!(
+                Export::from_args(&args(invalid)).ok().is_none(),
+                "{invalid:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_ppm() {
+        let mut out = Vec::new();
+        export("out.ppm", 2)
+            .write(&mut out, &canvas())
+            .expect("writing to a vector");
+
+        let (header, pixels) = out.split_at(11);
+        assert_eq!(header, b"P6\n4 2\n255\n");
+        let background = Palette::DARK.background;
+        let red = Palette::DARK.color(Color::Red);
+        let row = [background, background, red, red].concat();
+        assert_eq!(pixels, [row.clone(), row].concat());
+    }
+
+    #[test]
+    fn test_png() {
+        let mut out = Vec::new();
+        export("out.png", 3)
+            .write(&mut out, &canvas())
+            .expect("writing to a vector");
+
+        assert_eq!(out.get(..8), Some(&b"\x89PNG\r\n\x1a\n"[..]));
+        // The IHDR chunk starts with the width and height
+        assert_eq!(out.get(16..24), Some(&[0, 0, 0, 6, 0, 0, 0, 3][..]));
+    }
+
+    #[test]
+    fn test_svg() {
+        let mut out = Vec::new();
+        export("out.svg", 10)
+            .write(&mut out, &canvas())
+            .expect("writing to a vector");
+        let svg = String::from_utf8(out).expect("the image is text");
+
+        assert!(svg.contains(r#"width="20" height="10""#));
+        assert!(svg.contains(r##"<rect width="20" height="10" fill="#181820"/>"##));
+        assert!(svg.contains(r##"<rect x="10" y="0" width="10" height="10" fill="#cd3131"/>"##));
+        assert!(svg.trim_end().ends_with("</svg>"));
+    }
+}
@@ -0,0 +1,207 @@
+//! Choosing and reading the puzzle input at runtime, and normalizing it so
+//! the days see the same text no matter which editor or platform saved it.
+
+use crate::args::Args;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io::{self, Read};
+use std::path::PathBuf;
+
+/// The byte order mark some editors put at the start of a file.
+const BYTE_ORDER_MARK: char = '\u{feff}';
+
+/// The command line argument that selects standard input.
+pub const STDIN_ARG: &str = "-";
+
+/// A day's sample input.
+///
+/// Use [`sample!`](crate::sample) to describe the `sample.txt` next to a
+/// day's manifest; with the day's `embed` feature its contents are compiled
+/// into the binary.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Sample {
+    /// Location of the sample file.
+    pub path: &'static str,
+    /// Contents of the sample file if embedded at compile time.
+    pub embedded: Option<&'static str>,
+}
+
+impl Sample {
+    /// The embedded contents, or the file read from disk.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`io::Error`] if the file cannot be read.
+    pub fn read(&self) -> io::Result<String> {
+        match self.embedded {
+            Some(contents) => Ok(contents.to_owned()),
+            None => fs::read_to_string(self.path),
+        }
+    }
+}
+
+/// Describes the `sample.txt` in the calling crate's manifest directory,
+/// embedding it when the crate's `embed` feature is enabled.
+#[macro_export]
+macro_rules! sample {
+    () => {
+        $crate::input::Sample {
+            path: concat!(env!("CARGO_MANIFEST_DIR"), "/sample.txt"),
+            #[cfg(feature = "embed")]
+            embedded: Some(include_str!(concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/sample.txt"
+            ))),
+            #[cfg(not(feature = "embed"))]
+            embedded: None,
+        }
+    };
+}
+
+/// Where to read the puzzle input from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+    Sample(Sample),
+    Stdin,
+    File(PathBuf),
+}
+
+impl Source {
+    /// Interprets a command line argument: `-` selects standard input, any
+    /// other value a file, and no argument the sample.
+    #[must_use]
+    pub fn from_arg(arg: Option<PathBuf>, sample: Sample) -> Self {
+        match arg {
+            None => Self::Sample(sample),
+            Some(path) if path.as_os_str() == STDIN_ARG => Self::Stdin,
+            Some(path) => Self::File(path),
+        }
+    }
+
+    /// Reads the whole input and [normalizes](normalize) it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`InputError`] if the input cannot be read.
+    pub fn read(&self) -> Result<String, InputError> {
+        let contents = match self {
+            Self::Sample(sample) => sample.read(),
+            Self::Stdin => {
+                let mut contents = String::new();
+                io::stdin()
+                    .lock()
+                    .read_to_string(&mut contents)
+                    .map(|_| contents)
+            }
+            Self::File(path) => fs::read_to_string(path),
+        };
+
+        contents
+            .map(|contents| normalize(&contents))
+            .map_err(|source| InputError {
+                origin: self.to_string(),
+                source,
+            })
+    }
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Sample(sample) => write!(formatter, "{}", sample.path),
+            Self::Stdin => formatter.write_str("standard input"),
+            Self::File(path) => write!(formatter, "{}", path.display()),
+        }
+    }
+}
+
+/// Strips a leading byte order mark, turns CRLF line endings into LF,
+/// removes trailing whitespace from every line and drops trailing blank
+/// lines. Non-empty results end with a single newline.
+#[must_use]
+pub fn normalize(raw: &str) -> String {
+    let text = raw.strip_prefix(BYTE_ORDER_MARK).unwrap_or(raw);
+    let mut normalized = text
+        .lines()
+        .map(str::trim_end)
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    normalized.truncate(normalized.trim_end().len());
+    if !normalized.is_empty() {
+        normalized.push('\n');
+    }
+
+    normalized
+}
+
+/// Reads the input named by the first positional command line argument,
+/// defaulting to `sample`.
+///
+/// # Errors
+///
+/// Returns an [`InputError`] if the input cannot be read.
+pub fn from_args(sample: Sample) -> Result<String, InputError> {
+    Source::from_arg(Args::from_env().positional(0).map(PathBuf::from), sample).read()
+}
+
+/// The puzzle input could not be read.
+#[derive(Debug)]
+pub struct InputError {
+    origin: String,
+    source: io::Error,
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(formatter, "failed to read {}: {}", self.origin, self.source)
+    }
+}
+
+impl Error for InputError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: Sample = Sample {
+        path: "sample.txt",
+        embedded: Some("1 2\n"),
+    };
+
+    #[test]
+    fn test_from_arg() {
+        assert_eq!(Source::from_arg(None, SAMPLE), Source::Sample(SAMPLE));
+        assert_eq!(
+            Source::from_arg(Some(PathBuf::from("-")), SAMPLE),
+            Source::Stdin
+        );
+        assert_eq!(
+            Source::from_arg(Some(PathBuf::from("input.txt")), SAMPLE),
+            Source::File(PathBuf::from("input.txt"))
+        );
+    }
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(normalize("\u{feff}1 2\r\n3 4  \r\n\r\n \n"), "1 2\n3 4\n");
+        assert_eq!(normalize("a\n\nb\t\n"), "a\n\nb\n");
+        assert_eq!(normalize("\r\n\n"), "");
+        assert_eq!(normalize("125 17"), "125 17\n");
+    }
+
+    #[test]
+    fn test_read() {
+        assert_eq!(Source::Sample(SAMPLE).read().ok().as_deref(), Some("1 2\n"));
+
+        let missing = Source::File(PathBuf::from("does/not/exist.txt")).read();
+        assert!(missing.err().is_some_and(|error| error
+            .to_string()
+            .starts_with("failed to read does/not/exist.txt")));
+    }
+}
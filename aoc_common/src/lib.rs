@@ -0,0 +1,27 @@
+//! Building blocks shared by the individual days.
+
+pub mod args;
+pub mod generate;
+pub mod geometry;
+pub mod grid;
+#[cfg(feature = "image")]
+pub mod image;
+pub mod input;
+pub mod memory;
+#[cfg(feature = "parallel")]
+pub mod parallel;
+pub mod parse;
+pub mod part;
+#[cfg(feature = "json")]
+pub mod record;
+pub mod render;
+pub mod simulation;
+pub mod solution;
+#[cfg(feature = "tui")]
+pub mod tui;
+
+pub use geometry::{Direction, Direction8, Point, SignedPoint, Vector};
+pub use grid::{Grid, GridError};
+pub use parse::ParseError;
+pub use part::Part;
+pub use solution::{Solution, Variant};
@@ -0,0 +1,170 @@
+//! Counting what a phase allocates.
+//!
+//! [`CountingAllocator`] forwards to the system allocator and keeps a tally
+//! of every allocation. A binary opts in by installing it:
+//!
+//! ```no_run
+//! use aoc_common::memory::CountingAllocator;
+//!
+//! #[global_allocator]
+//! static ALLOCATOR: CountingAllocator = CountingAllocator;
+//! ```
+//!
+//! The tally is shared by all threads, so a [`Measurement`] includes the
+//! allocations of the parallel iterators a phase starts. Only one phase can
+//! be measured at a time.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+static COUNTING: AtomicBool = AtomicBool::new(false);
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static ALLOCATED_BYTES: AtomicUsize = AtomicUsize::new(0);
+static CURRENT_BYTES: AtomicUsize = AtomicUsize::new(0);
+static PEAK_BYTES: AtomicUsize = AtomicUsize::new(0);
+
+/// The system allocator, counting allocations, allocated bytes and the
+/// most bytes in use at once.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CountingAllocator;
+
+fn record_allocation(size: usize) {
+    COUNTING.store(true, Ordering::Relaxed);
+    ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+    ALLOCATED_BYTES.fetch_add(size, Ordering::Relaxed);
+    let current = CURRENT_BYTES.fetch_add(size, Ordering::Relaxed) + size;
+    PEAK_BYTES.fetch_max(current, Ordering::Relaxed);
+}
+
+fn record_deallocation(size: usize) {
+    CURRENT_BYTES.fetch_sub(size, Ordering::Relaxed);
+}
+
+#[expect(
+    unsafe_code,
+    reason = "a global allocator has to implement the unsafe GlobalAlloc trait"
+)]
+// SAFETY: every method forwards to the system allocator with the arguments
+// it was called with, so the caller's guarantees carry over.
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        // SAFETY: forwarded with the caller's layout.
+        let pointer = unsafe { System.alloc(layout) };
+        if !pointer.is_null() {
+            record_allocation(layout.size());
+        }
+        pointer
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        // SAFETY: forwarded with the caller's layout.
+        let pointer = unsafe { System.alloc_zeroed(layout) };
+        if !pointer.is_null() {
+            record_allocation(layout.size());
+        }
+        pointer
+    }
+
+    unsafe fn dealloc(&self, pointer: *mut u8, layout: Layout) {
+        // SAFETY: the pointer was allocated by `System` with this layout.
+        unsafe {
+            System.dealloc(pointer, layout);
+        }
+        record_deallocation(layout.size());
+    }
+
+    /// Counts as freeing the old block and allocating the new one.
+    unsafe fn realloc(&self, pointer: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        // SAFETY: the pointer was allocated by `System` with this layout.
+        let new_pointer = unsafe { System.realloc(pointer, layout, new_size) };
+        if !new_pointer.is_null() {
+            record_deallocation(layout.size());
+            record_allocation(new_size);
+        }
+        new_pointer
+    }
+}
+
+/// What a phase allocated.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Allocations {
+    pub count: usize,
+    pub bytes: usize,
+    /// Most bytes in use at once on top of those in use when the phase
+    /// started.
+    pub peak_bytes: usize,
+}
+
+/// Counts allocations from [`Self::start`] until [`Self::finish`].
+#[derive(Debug)]
+pub struct Measurement {
+    count: usize,
+    bytes: usize,
+    current_bytes: usize,
+}
+
+impl Measurement {
+    /// Starts counting, or returns `None` if the [`CountingAllocator`] is not
+    /// the global allocator.
+    #[must_use]
+    pub fn start() -> Option<Self> {
+        if !is_counting() {
+            return None;
+        }
+
+        let current_bytes = CURRENT_BYTES.load(Ordering::Relaxed);
+        PEAK_BYTES.store(current_bytes, Ordering::Relaxed);
+        Some(Self {
+            count: ALLOCATIONS.load(Ordering::Relaxed),
+            bytes: ALLOCATED_BYTES.load(Ordering::Relaxed),
+            current_bytes,
+        })
+    }
+
+    #[must_use]
+    pub fn finish(self) -> Allocations {
+        Allocations {
+            count: ALLOCATIONS.load(Ordering::Relaxed) - self.count,
+            bytes: ALLOCATED_BYTES.load(Ordering::Relaxed) - self.bytes,
+            peak_bytes: PEAK_BYTES
+                .load(Ordering::Relaxed)
+                .saturating_sub(self.current_bytes),
+        }
+    }
+}
+
+/// Whether allocations go through the [`CountingAllocator`], which is
+/// known once it has allocated anything.
+#[must_use]
+pub fn is_counting() -> bool {
+    COUNTING.load(Ordering::Relaxed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::hint;
+
+    #[global_allocator]
+    static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+    #[test]
+    fn test_measurement() {
+        drop(hint::black_box(Box::new(0_u8)));
+        assert!(is_counting());
+
+        // Other tests may allocate at the same time, so only lower bounds hold
+        let measurement = Measurement::start().expect("the allocator is installed");
+        let mut numbers = hint::black_box(Vec::<u64>::with_capacity(1000));
+        numbers.extend(0..2000);
+        drop(numbers);
+        let allocations = measurement.finish();
+
+        assert!(allocations.count >= 2, "allocating and growing the vector");
+        assert!(
+            allocations.bytes >= 24_000,
+            "8000 bytes and then 16000 bytes"
+        );
+        assert!(allocations.peak_bytes >= 16_000, "the grown vector");
+    }
+}
@@ -0,0 +1,180 @@
+//! How many threads the days may use for their parallel iterators.
+//!
+//! Every rayon call of the days runs on rayon's global thread pool, so
+//! configuring that pool once at startup covers nested parallel iterators
+//! as well. A single thread runs all work one item after another, which
+//! makes profiles reproducible and gives the baseline for the speedup of
+//! more threads.
+
+use rayon::{ThreadPoolBuildError, ThreadPoolBuilder};
+use std::env::{self, VarError};
+use std::error::Error;
+use std::fmt;
+use std::num::NonZeroUsize;
+use std::str::FromStr;
+
+/// Sets the parallelism: a number of threads, `sequential`, or `auto`.
+pub const THREADS_VAR: &str = "AOC_THREADS";
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Parallelism {
+    /// One thread per CPU, as rayon picks by default.
+    #[default]
+    Auto,
+    /// A single worker thread.
+    Sequential,
+    Threads(NonZeroUsize),
+}
+
+impl Parallelism {
+    /// The parallelism set by [`THREADS_VAR`], or [`Self::Auto`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ParallelismError::Invalid`] if the variable is set to
+    /// anything else than a parallelism.
+    pub fn from_env() -> Result<Self, ParallelismError> {
+        match env::var(THREADS_VAR) {
+            Ok(value) => value.parse(),
+            Err(VarError::NotPresent) => Ok(Self::Auto),
+            Err(VarError::NotUnicode(value)) => Err(ParallelismError::Invalid(
+                value.to_string_lossy().into_owned(),
+            )),
+        }
+    }
+
+    /// Number of worker threads, or `None` to leave it to rayon.
+    #[must_use]
+    pub fn threads(self) -> Option<usize> {
+        match self {
+            Self::Auto => None,
+            Self::Sequential => Some(1),
+            Self::Threads(threads) => Some(threads.get()),
+        }
+    }
+
+    /// Makes every parallel iterator of the process use this many threads.
+    /// Has to happen before the first parallel iterator runs.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ParallelismError::Pool`] if the thread pool is already
+    /// running or its threads cannot be started.
+    pub fn configure(self) -> Result<(), ParallelismError> {
+        let Some(threads) = self.threads() else {
+            return Ok(());
+        };
+        ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .build_global()
+            .map_err(ParallelismError::Pool)
+    }
+}
+
+impl FromStr for Parallelism {
+    type Err = ParallelismError;
+
+    /// Parses `auto`, `sequential` or a number of threads, where `0` means
+    /// `auto` and `1` means `sequential`.
+    fn from_str(value: &str) -> Result<Self, ParallelismError> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "auto" | "" => Ok(Self::Auto),
+            "sequential" => Ok(Self::Sequential),
+            threads => match threads.parse().map(NonZeroUsize::new) {
+                Ok(None) => Ok(Self::Auto),
+                Ok(Some(threads)) if threads.get() == 1 => Ok(Self::Sequential),
+                Ok(Some(threads)) => Ok(Self::Threads(threads)),
+                Err(_parse_error) => Err(ParallelismError::Invalid(value.to_owned())),
+            },
+        }
+    }
+}
+
+impl fmt::Display for Parallelism {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Auto => formatter.pad("auto"),
+            Self::Sequential => formatter.pad("sequential"),
+            Self::Threads(threads) => write!(formatter, "{threads} threads"),
+        }
+    }
+}
+
+/// Reads [`THREADS_VAR`] and configures the thread pool with it.
+///
+/// # Errors
+///
+/// Returns a [`ParallelismError`] if the variable is invalid or the pool
+/// cannot be configured.
+pub fn configure_from_env() -> Result<Parallelism, ParallelismError> {
+    let parallelism = Parallelism::from_env()?;
+    parallelism.configure()?;
+    Ok(parallelism)
+}
+
+#[derive(Debug)]
+pub enum ParallelismError {
+    /// Neither a number of threads nor `auto` or `sequential`.
+    Invalid(String),
+    Pool(ThreadPoolBuildError),
+}
+
+impl fmt::Display for ParallelismError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Invalid(value) => write!(
+                formatter,
+                "invalid parallelism {value:?}, expected a number of threads, \
+                 `auto` or `sequential`"
+            ),
+            Self::Pool(source) => {
+                write!(formatter, "cannot configure the thread pool: {source}")
+            }
+        }
+    }
+}
+
+impl Error for ParallelismError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Invalid(_) => None,
+            Self::Pool(source) => Some(source),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!("auto".parse::<Parallelism>().ok(), Some(Parallelism::Auto));
+        assert_eq!("0".parse::<Parallelism>().ok(), Some(Parallelism::Auto));
+        assert_eq!(
+            "Sequential".parse::<Parallelism>().ok(),
+            Some(Parallelism::Sequential)
+        );
+        assert_eq!(
+            "1".parse::<Parallelism>().ok(),
+            Some(Parallelism::Sequential)
+        );
+        assert_eq!(
+            "4".parse::<Parallelism>().ok(),
+            NonZeroUsize::new(4).map(Parallelism::Threads)
+        );
+        assert!(matches!(
+            "many".parse::<Parallelism>(),
+            Err(ParallelismError::Invalid(_))
+        ));
+
+        assert_eq!(Parallelism::Sequential.threads(), Some(1));
+        assert_eq!(Parallelism::Auto.threads(), None);
+        assert_eq!(
+            "4".parse::<Parallelism>()
+                .map(|parallelism| parallelism.to_string())
+                .ok(),
+            Some("4 threads".to_owned())
+        );
+    }
+}
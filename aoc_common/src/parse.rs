@@ -0,0 +1,336 @@
+//! Helpers for parsing puzzle input that keep track of where every piece of
+//! text came from, so a malformed input is reported with the offending line
+//! and column instead of a panic.
+
+use crate::grid::GridError;
+use std::error::Error;
+use std::fmt::{self, Display};
+use std::iter;
+use std::str::FromStr;
+
+/// A location in the input, both counted from zero.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Location {
+    pub line: usize,
+    pub column: usize,
+}
+
+/// The input could not be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// Where the problem is, if it can be pinned to a single place.
+    pub location: Option<Location>,
+    /// What is wrong with the input.
+    pub reason: String,
+}
+
+impl ParseError {
+    /// An error at `location`.
+    pub fn new(location: Location, reason: impl Into<String>) -> Self {
+        Self {
+            location: Some(location),
+            reason: reason.into(),
+        }
+    }
+
+    /// An error that concerns the input as a whole.
+    pub fn unlocated(reason: impl Into<String>) -> Self {
+        Self {
+            location: None,
+            reason: reason.into(),
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.location {
+            Some(Location { line, column }) => write!(
+                formatter,
+                "line {}, column {}: {}",
+                line + 1,
+                column + 1,
+                self.reason
+            ),
+            None => formatter.write_str(&self.reason),
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+impl From<GridError> for ParseError {
+    fn from(error: GridError) -> Self {
+        match error {
+            GridError::Ragged {
+                row,
+                expected,
+                found,
+            } => Self::new(
+                Location {
+                    line: row,
+                    column: 0,
+                },
+                format!("expected {expected} tiles but found {found}"),
+            ),
+            GridError::InvalidTile { row, column, tile } => Self::new(
+                Location { line: row, column },
+                format!("invalid tile {tile:?}"),
+            ),
+        }
+    }
+}
+
+/// A piece of the input together with the location of its first character.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Token<'input> {
+    pub text: &'input str,
+    pub location: Location,
+}
+
+/// Splits `input` into one token per line.
+pub fn lines(input: &str) -> impl Iterator<Item = Token<'_>> {
+    input.lines().enumerate().map(|(line, text)| Token {
+        text,
+        location: Location { line, column: 0 },
+    })
+}
+
+/// Splits `input` into paragraphs: runs of non-blank lines separated by one
+/// or more blank lines.
+pub fn paragraphs(input: &str) -> impl Iterator<Item = Vec<Token<'_>>> {
+    let is_blank = |line: &Token<'_>| line.text.trim().is_empty();
+    let mut lines = lines(input).peekable();
+
+    iter::from_fn(move || {
+        while lines.next_if(is_blank).is_some() {}
+        let paragraph = iter::from_fn(|| lines.next_if(|line| !is_blank(line))).collect::<Vec<_>>();
+        (!paragraph.is_empty()).then_some(paragraph)
+    })
+}
+
+/// Unpacks a paragraph that must have exactly `N` lines.
+///
+/// # Errors
+///
+/// Returns a [`ParseError`] at the paragraph's first line if it has more or
+/// fewer lines.
+pub fn paragraph_lines<const N: usize>(
+    paragraph: Vec<Token<'_>>,
+) -> Result<[Token<'_>; N], ParseError> {
+    paragraph
+        .try_into()
+        .map_err(|paragraph: Vec<Token<'_>>| ParseError {
+            location: paragraph.first().map(|line| line.location),
+            reason: format!("expected {N} lines but found {}", paragraph.len()),
+        })
+}
+
+impl<'input> Token<'input> {
+    /// An error pointing at the start of this token.
+    pub fn error(&self, reason: impl Into<String>) -> ParseError {
+        ParseError::new(self.location, reason)
+    }
+
+    /// The token starting `columns` characters further right.
+    fn advanced(&self, columns: usize, text: &'input str) -> Self {
+        Self {
+            text,
+            location: Location {
+                line: self.location.line,
+                column: self.location.column + columns,
+            },
+        }
+    }
+
+    /// Parses the whole token with [`FromStr`].
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ParseError`] at this token if it is not a valid `T`.
+    pub fn parse<T>(&self) -> Result<T, ParseError>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        self.text
+            .parse()
+            .map_err(|error| self.error(format!("cannot parse {:?}: {error}", self.text)))
+    }
+
+    /// Splits the token at every `separator`.
+    pub fn split(&self, separator: char) -> impl Iterator<Item = Self> + 'input {
+        self.split_by(move |char| char == separator)
+    }
+
+    /// Splits the token at runs of whitespace, skipping empty pieces.
+    pub fn split_whitespace(&self) -> impl Iterator<Item = Self> + 'input {
+        self.split_by(char::is_whitespace)
+            .filter(|token| !token.text.is_empty())
+    }
+
+    fn split_by<P>(&self, is_separator: P) -> impl Iterator<Item = Self> + 'input
+    where
+        P: FnMut(char) -> bool + 'input,
+    {
+        let token = *self;
+        let mut column = 0;
+        self.text.split(is_separator).map(move |text| {
+            let piece = token.advanced(column, text);
+            // Every piece is followed by exactly one separator character
+            column += text.chars().count() + 1;
+            piece
+        })
+    }
+
+    /// Splits the token at the first `separator`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ParseError`] if the token does not contain `separator`.
+    pub fn split_once(&self, separator: char) -> Result<(Self, Self), ParseError> {
+        let (head, tail) = self
+            .text
+            .split_once(separator)
+            .ok_or_else(|| self.error(format!("expected {separator:?} in {:?}", self.text)))?;
+
+        Ok((
+            self.advanced(0, head),
+            self.advanced(head.chars().count() + 1, tail),
+        ))
+    }
+
+    /// Removes `prefix` from the start of the token.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ParseError`] if the token does not start with `prefix`.
+    pub fn strip_prefix(&self, prefix: &str) -> Result<Self, ParseError> {
+        self.text
+            .strip_prefix(prefix)
+            .map(|text| self.advanced(prefix.chars().count(), text))
+            .ok_or_else(|| self.error(format!("expected {prefix:?}, found {:?}", self.text)))
+    }
+
+    /// Removes leading and trailing whitespace.
+    #[must_use]
+    pub fn trim(&self) -> Self {
+        let start = self.text.trim_start();
+        let skipped = self.text.chars().count() - start.chars().count();
+        self.advanced(skipped, start.trim_end())
+    }
+
+    /// The characters of the token together with their own locations.
+    pub fn chars(&self) -> impl Iterator<Item = (Location, char)> + 'input {
+        let Location { line, column } = self.location;
+        self.text.chars().enumerate().map(move |(offset, char)| {
+            let location = Location {
+                line,
+                column: column + offset,
+            };
+            (location, char)
+        })
+    }
+}
+
+/// Collects exactly `N` tokens, failing if there are more or fewer.
+///
+/// # Errors
+///
+/// Returns a [`ParseError`] at `whole` naming `what` was expected.
+pub fn exactly<'input, const N: usize>(
+    whole: &Token<'input>,
+    tokens: impl Iterator<Item = Token<'input>>,
+    what: &str,
+) -> Result<[Token<'input>; N], ParseError> {
+    tokens
+        .collect::<Vec<_>>()
+        .try_into()
+        .map_err(|tokens: Vec<_>| {
+            whole.error(format!("expected {N} {what} but found {}", tokens.len()))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_locations() {
+        let input = "ignored\n  12, x";
+        let line = lines(input).nth(1).expect("two lines");
+        let [number, letter] =
+            exactly(&line, line.split(','), "fields").expect("two comma-separated fields");
+
+        assert_eq!(number.trim().parse::<u32>(), Ok(12));
+        assert_eq!(
+            letter
+                .trim()
+                .parse::<u32>()
+                .map_err(|error| error.to_string()),
+            Err("line 2, column 7: cannot parse \"x\": invalid digit found in string".to_owned())
+        );
+        assert_eq!(
+            exactly::<3>(&line, line.split(','), "fields").map_err(|error| error.to_string()),
+            Err("line 2, column 1: expected 3 fields but found 2".to_owned())
+        );
+    }
+
+    #[test]
+    fn test_prefixes() {
+        let token = lines("X+94, Y+34").next().expect("one line");
+        let (x, y) = token.split_once(',').expect("a comma");
+
+        assert_eq!(x.strip_prefix("X+").and_then(|x| x.parse()), Ok(94));
+        assert_eq!(
+            y.trim().strip_prefix("Y+").map(|y| y.location.column),
+            Ok(8)
+        );
+        assert_eq!(
+            x.strip_prefix("Y+").map_err(|error| error.location),
+            Err(Some(Location { line: 0, column: 0 }))
+        );
+        assert!(token.split_once('|').ok().is_none());
+    }
+
+    #[test]
+    fn test_paragraphs() {
+        let input = "\na\nb\n\n  \n\nc\n";
+        let paragraphs = paragraphs(input)
+            .map(|paragraph| {
+                paragraph
+                    .iter()
+                    .map(|line| (line.location.line, line.text))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(paragraphs, [vec![(1, "a"), (2, "b")], vec![(6, "c")]]);
+
+        let [first, second] = super::paragraphs(input)
+            .next()
+            .map(paragraph_lines)
+            .expect("a first paragraph")
+            .expect("two lines");
+        assert_eq!((first.text, second.text), ("a", "b"));
+        assert_eq!(
+            super::paragraphs(input)
+                .nth(1)
+                .map(paragraph_lines::<2>)
+                .and_then(Result::err)
+                .map(|error| error.to_string()),
+            Some("line 7, column 1: expected 2 lines but found 1".to_owned())
+        );
+    }
+
+    #[test]
+    fn test_from_grid_error() {
+        let error = ParseError::from(GridError::InvalidTile {
+            row: 2,
+            column: 4,
+            tile: '?',
+        });
+
+        assert_eq!(error.to_string(), "line 3, column 5: invalid tile '?'");
+    }
+}
@@ -0,0 +1,55 @@
+//! The two parts every puzzle consists of.
+
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const ALL: [Self; 2] = [Self::One, Self::Two];
+
+    #[must_use]
+    pub const fn number(self) -> u8 {
+        match self {
+            Self::One => 1,
+            Self::Two => 2,
+        }
+    }
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.pad(match self {
+            Self::One => "1",
+            Self::Two => "2",
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParsePartError(String);
+
+impl fmt::Display for ParsePartError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(formatter, "invalid part {:?}, expected 1 or 2", self.0)
+    }
+}
+
+impl Error for ParsePartError {}
+
+impl FromStr for Part {
+    type Err = ParsePartError;
+
+    fn from_str(part: &str) -> Result<Self, Self::Err> {
+        match part.trim().to_ascii_lowercase().as_str() {
+            "1" | "one" => Ok(Self::One),
+            "2" | "two" => Ok(Self::Two),
+            _ => Err(ParsePartError(part.to_owned())),
+        }
+    }
+}
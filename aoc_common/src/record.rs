@@ -0,0 +1,244 @@
+//! The JSON records of a run, one per part.
+//!
+//! `aoc run --format json` and the `--format json` of every day's binary
+//! print the same records, so scripts can read either.
+
+use crate::args::Args;
+use crate::memory::{Allocations, Measurement};
+use crate::{Part, Solution};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::error::Error;
+use std::fmt::{self, Write as _};
+use std::io::{self, Write};
+use std::time::{Duration, Instant};
+
+/// The result of one part of a day.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Record {
+    pub day: u8,
+    pub name: &'static str,
+    /// The part, or `None` if the day failed before solving any part.
+    pub part: Option<u8>,
+    pub answer: Option<String>,
+    pub error: Option<String>,
+    pub parse_nanos: Option<u64>,
+    pub solve_nanos: Option<u64>,
+    /// What parsing allocated, if allocations were counted.
+    pub parse_memory: Option<MemoryRecord>,
+    /// What solving the part allocated, if allocations were counted.
+    pub solve_memory: Option<MemoryRecord>,
+    /// Hex-encoded SHA-256 digest of the input.
+    pub input_sha256: Option<String>,
+}
+
+/// What a phase allocated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct MemoryRecord {
+    pub allocations: usize,
+    pub bytes: usize,
+    pub peak_bytes: usize,
+}
+
+impl From<&Allocations> for MemoryRecord {
+    fn from(allocations: &Allocations) -> Self {
+        Self {
+            allocations: allocations.count,
+            bytes: allocations.bytes,
+            peak_bytes: allocations.peak_bytes,
+        }
+    }
+}
+
+/// `duration` in whole nanoseconds, saturating far beyond any run.
+#[must_use]
+pub fn nanos(duration: Duration) -> u64 {
+    u64::try_from(duration.as_nanos()).unwrap_or(u64::MAX)
+}
+
+/// Hex-encoded SHA-256 digest of `input`, identifying it across runs.
+#[must_use]
+pub fn hash_input(input: &str) -> String {
+    Sha256::digest(input)
+        .iter()
+        .fold(String::with_capacity(64), |mut hash, byte| {
+            write!(hash, "{byte:02x}").expect("writing to a string");
+            hash
+        })
+}
+
+/// Parses `input` and solves both parts of `S`, timing every phase. `name`
+/// is the day's crate name.
+#[must_use]
+pub fn solve<S: Solution>(name: &'static str, input: &str) -> Vec<Record> {
+    let record = Record {
+        day: S::DAY,
+        name,
+        part: None,
+        answer: None,
+        error: None,
+        parse_nanos: None,
+        solve_nanos: None,
+        parse_memory: None,
+        solve_memory: None,
+        input_sha256: Some(hash_input(input)),
+    };
+
+    let parse_measurement = Measurement::start();
+    let parse_start = Instant::now();
+    let parsed = S::parse(input);
+    let parse_nanos = Some(nanos(parse_start.elapsed()));
+    let parse_memory =
+        parse_measurement.map(|measurement| MemoryRecord::from(&measurement.finish()));
+    let parsed = match parsed {
+        Ok(parsed) => parsed,
+        Err(error) => {
+            return vec![Record {
+                error: Some(format!("invalid input: {error}")),
+                ..record
+            }]
+        }
+    };
+
+    Part::ALL
+        .into_iter()
+        .map(|part| {
+            let measurement = Measurement::start();
+            let start = Instant::now();
+            let answer = S::solve(&parsed, part);
+            Record {
+                part: Some(part.number()),
+                answer: Some(answer),
+                parse_nanos,
+                solve_nanos: Some(nanos(start.elapsed())),
+                parse_memory,
+                solve_memory: measurement
+                    .map(|measurement| MemoryRecord::from(&measurement.finish())),
+                ..record.clone()
+            }
+        })
+        .collect()
+}
+
+/// Writes `records` as a JSON array.
+///
+/// # Errors
+///
+/// Returns any error from writing to `out`.
+pub fn write_json(out: &mut impl Write, records: &[Record]) -> io::Result<()> {
+    serde_json::to_writer_pretty(&mut *out, records)?;
+    writeln!(out)
+}
+
+/// Solves `input` with `S` and prints the records to stdout, for the
+/// `--format json` of a day's binary.
+///
+/// # Errors
+///
+/// Returns any error from writing to stdout.
+pub fn print_json<S: Solution>(name: &'static str, input: &str) -> io::Result<()> {
+    write_json(&mut io::stdout().lock(), &solve::<S>(name, input))
+}
+
+/// Whether `args` ask for records with `--format json` instead of the day's
+/// own output, which is `--format text`.
+///
+/// # Errors
+///
+/// Returns a [`FormatError`] for any other format.
+pub fn json_requested(args: &Args) -> Result<bool, FormatError> {
+    match args.option("format").map(|format| format.to_str()) {
+        None | Some(Some("text")) => Ok(false),
+        Some(Some("json")) => Ok(true),
+        Some(format) => Err(FormatError(format.unwrap_or_default().to_owned())),
+    }
+}
+
+/// A `--format` other than `text` or `json`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FormatError(pub String);
+
+impl fmt::Display for FormatError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            formatter,
+            "unknown format {:?}, expected `text` or `json`",
+            self.0
+        )
+    }
+}
+
+impl Error for FormatError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse::ParseError;
+    use std::ffi::OsString;
+    use std::fmt::Display;
+
+    struct Sum;
+
+    impl Solution for Sum {
+        const DAY: u8 = 1;
+        const TITLE: &'static str = "Sum";
+
+        type Input = Vec<u32>;
+        type Error = ParseError;
+
+        fn parse(input: &str) -> Result<Vec<u32>, ParseError> {
+            input
+                .split_whitespace()
+                .map(|number| {
+                    number
+                        .parse()
+                        .map_err(|_error| ParseError::unlocated("expected a number"))
+                })
+                .collect()
+        }
+
+        fn part_one(numbers: &Vec<u32>) -> impl Display {
+            numbers.iter().sum::<u32>()
+        }
+
+        fn part_two(numbers: &Vec<u32>) -> impl Display {
+            numbers.iter().product::<u32>()
+        }
+    }
+
+    #[test]
+    fn test_hash_input() {
+        assert_eq!(
+            hash_input("abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+
+    #[test]
+    fn test_solve() {
+        let records = solve::<Sum>("sum", "2 3 4");
+        let answers = records
+            .iter()
+            .map(|record| (record.part, record.answer.as_deref()))
+            .collect::<Vec<_>>();
+        assert_eq!(answers, [(Some(1), Some("9")), (Some(2), Some("24"))]);
+        assert!(records.iter().all(|record| record.solve_nanos.is_some()));
+
+        let failed = solve::<Sum>("sum", "2 x");
+        assert_eq!(failed.len(), 1);
+        assert_eq!(failed.first().and_then(|record| record.part), None);
+        assert!(failed.first().is_some_and(|record| record.error.is_some()));
+    }
+
+    #[test]
+    fn test_json_requested() {
+        let args = |args: &[&str]| Args::parse(args.iter().map(OsString::from));
+        assert_eq!(json_requested(&args(&["input.txt"])), Ok(false));
+        assert_eq!(json_requested(&args(&["--format", "text"])), Ok(false));
+        assert_eq!(json_requested(&args(&["--format=json"])), Ok(true));
+        assert_eq!(
+            json_requested(&args(&["--format", "yaml"])),
+            Err(FormatError("yaml".to_owned()))
+        );
+    }
+}
@@ -0,0 +1,518 @@
+//! Drawing grids in the terminal with colors, overlays and a legend.
+//!
+//! A [`Canvas`] holds one styled glyph per tile. Days build it from their
+//! grid, mark paths or highlight regions on top, and hand one or more
+//! canvases to a [`Renderer`], which lays them out side by side.
+//!
+//! How the renderer draws is picked with the `AOC_RENDER` environment
+//! variable: `color`, `plain` (no escape codes) or `off` (draw nothing). By
+//! default it draws in color when standard output is a terminal and
+//! `NO_COLOR` is not set.
+
+use crate::geometry::Point;
+use crate::grid::Grid;
+use std::env;
+use std::io::{self, IsTerminal, Write};
+
+/// The environment variable selecting the [`Mode`].
+pub const MODE_VAR: &str = "AOC_RENDER";
+
+/// One of the sixteen colors every terminal supports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Color {
+    Black,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+    BrightBlack,
+    BrightRed,
+    BrightGreen,
+    BrightYellow,
+    BrightBlue,
+    BrightMagenta,
+    BrightCyan,
+    BrightWhite,
+}
+
+impl Color {
+    pub const ALL: [Self; 16] = [
+        Self::Black,
+        Self::Red,
+        Self::Green,
+        Self::Yellow,
+        Self::Blue,
+        Self::Magenta,
+        Self::Cyan,
+        Self::White,
+        Self::BrightBlack,
+        Self::BrightRed,
+        Self::BrightGreen,
+        Self::BrightYellow,
+        Self::BrightBlue,
+        Self::BrightMagenta,
+        Self::BrightCyan,
+        Self::BrightWhite,
+    ];
+
+    /// Distinct colors for telling apart many things, such as regions.
+    pub const PALETTE: [Self; 12] = [
+        Self::Red,
+        Self::Green,
+        Self::Yellow,
+        Self::Blue,
+        Self::Magenta,
+        Self::Cyan,
+        Self::BrightRed,
+        Self::BrightGreen,
+        Self::BrightYellow,
+        Self::BrightBlue,
+        Self::BrightMagenta,
+        Self::BrightCyan,
+    ];
+
+    /// The color of palette entry `index`, repeating the palette.
+    #[must_use]
+    pub fn cycle(index: usize) -> Self {
+        Self::PALETTE
+            .get(index % Self::PALETTE.len())
+            .copied()
+            .unwrap_or(Self::White)
+    }
+
+    /// Offset of the color from the first code of its SGR range.
+    const fn offset(self) -> u8 {
+        match self {
+            Self::Black => 0,
+            Self::Red => 1,
+            Self::Green => 2,
+            Self::Yellow => 3,
+            Self::Blue => 4,
+            Self::Magenta => 5,
+            Self::Cyan => 6,
+            Self::White => 7,
+            Self::BrightBlack => 60,
+            Self::BrightRed => 61,
+            Self::BrightGreen => 62,
+            Self::BrightYellow => 63,
+            Self::BrightBlue => 64,
+            Self::BrightMagenta => 65,
+            Self::BrightCyan => 66,
+            Self::BrightWhite => 67,
+        }
+    }
+}
+
+/// How a glyph is drawn.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Style {
+    pub foreground: Option<Color>,
+    pub background: Option<Color>,
+    pub bold: bool,
+    pub dim: bool,
+}
+
+impl Style {
+    /// The terminal's default look.
+    pub const PLAIN: Self = Self {
+        foreground: None,
+        background: None,
+        bold: false,
+        dim: false,
+    };
+
+    #[must_use]
+    pub const fn fg(color: Color) -> Self {
+        Self {
+            foreground: Some(color),
+            ..Self::PLAIN
+        }
+    }
+
+    #[must_use]
+    pub const fn on(self, color: Color) -> Self {
+        Self {
+            background: Some(color),
+            ..self
+        }
+    }
+
+    #[must_use]
+    pub const fn bold(self) -> Self {
+        Self { bold: true, ..self }
+    }
+
+    #[must_use]
+    pub const fn dim(self) -> Self {
+        Self { dim: true, ..self }
+    }
+
+    /// The SGR escape sequence switching from any style to this one.
+    fn escape(self) -> String {
+        let mut codes = vec!["0".to_owned()];
+        if self.bold {
+            codes.push("1".to_owned());
+        }
+        if self.dim {
+            codes.push("2".to_owned());
+        }
+        if let Some(color) = self.foreground {
+            codes.push((30 + color.offset()).to_string());
+        }
+        if let Some(color) = self.background {
+            codes.push((40 + color.offset()).to_string());
+        }
+        format!("\x1b[{}m", codes.join(";"))
+    }
+}
+
+/// A glyph together with its style.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Cell {
+    pub glyph: char,
+    pub style: Style,
+}
+
+impl Cell {
+    #[must_use]
+    pub const fn new(glyph: char, style: Style) -> Self {
+        Self { glyph, style }
+    }
+
+    #[must_use]
+    pub const fn plain(glyph: char) -> Self {
+        Self::new(glyph, Style::PLAIN)
+    }
+}
+
+/// A grid of styled glyphs with a title, ready to be drawn.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Canvas {
+    pub title: String,
+    cells: Grid<Cell>,
+}
+
+impl Canvas {
+    /// A `width` x `height` canvas filled with `background`.
+    #[must_use]
+    pub fn new(width: usize, height: usize, background: Cell) -> Self {
+        Self {
+            title: String::new(),
+            cells: Grid::new(width, height, background),
+        }
+    }
+
+    /// Draws every tile of `grid` as the cell `cell` returns for it.
+    #[must_use]
+    pub fn from_grid<T, F>(grid: &Grid<T>, mut cell: F) -> Self
+    where
+        F: FnMut(Point, &T) -> Cell,
+    {
+        let mut canvas = Self::new(grid.width(), grid.height(), Cell::plain(' '));
+        for (point, tile) in grid.iter() {
+            canvas.set(point, cell(point, tile));
+        }
+        canvas
+    }
+
+    #[must_use]
+    pub fn titled(self, title: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            ..self
+        }
+    }
+
+    #[must_use]
+    pub fn width(&self) -> usize {
+        self.cells.width()
+    }
+
+    #[must_use]
+    pub fn height(&self) -> usize {
+        self.cells.height()
+    }
+
+    #[must_use]
+    pub fn get(&self, point: Point) -> Option<&Cell> {
+        self.cells.get(point)
+    }
+
+    /// Replaces the cell at `point`, ignoring points outside the canvas.
+    pub fn set(&mut self, point: Point, cell: Cell) {
+        if let Some(slot) = self.cells.get_mut(point) {
+            *slot = cell;
+        }
+    }
+
+    /// Draws `cell` at every point, such as the steps of a path or markers.
+    pub fn mark(&mut self, points: impl IntoIterator<Item = Point>, cell: Cell) {
+        for point in points {
+            self.set(point, cell);
+        }
+    }
+
+    /// Restyles the cells at every point while keeping their glyphs, such as
+    /// a highlighted region.
+    pub fn highlight(&mut self, points: impl IntoIterator<Item = Point>, style: Style) {
+        for point in points {
+            if let Some(slot) = self.cells.get_mut(point) {
+                slot.style = style;
+            }
+        }
+    }
+
+    /// The cells of row `y` from left to right, or none past the bottom.
+    #[must_use]
+    pub fn row(&self, y: usize) -> &[Cell] {
+        self.cells.row(y).unwrap_or_default()
+    }
+
+    /// The top-left part of the canvas, at most `width` x `height` cells.
+    #[must_use]
+    pub fn crop(&self, width: usize, height: usize) -> Self {
+        let mut cropped = Self::new(
+            self.width().min(width),
+            self.height().min(height),
+            Cell::plain(' '),
+        )
+        .titled(self.title.clone());
+        for (point, &cell) in self.cells.iter() {
+            cropped.set(point, cell);
+        }
+        cropped
+    }
+
+    /// Places `other` to the right of this canvas, `gap` blank cells apart,
+    /// for images of several canvases.
+    #[must_use]
+    pub fn beside(&self, other: &Self, gap: usize) -> Self {
+        let offset = self.width() + gap;
+        let mut combined = Self::new(
+            offset + other.width(),
+            self.height().max(other.height()),
+            Cell::plain(' '),
+        )
+        .titled(self.title.clone());
+
+        for (point, &cell) in self.cells.iter() {
+            combined.set(point, cell);
+        }
+        for (point, &cell) in other.cells.iter() {
+            combined.set(Point::new(point.x + offset, point.y), cell);
+        }
+        combined
+    }
+}
+
+/// How a [`Renderer`] draws.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    /// Glyphs with ANSI colors and styles.
+    Color,
+    /// Glyphs only.
+    Plain,
+    /// Nothing at all.
+    Off,
+}
+
+/// Draws canvases next to each other, followed by a legend.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Renderer {
+    pub mode: Mode,
+}
+
+impl Renderer {
+    #[must_use]
+    pub const fn new(mode: Mode) -> Self {
+        Self { mode }
+    }
+
+    /// Picks the mode from [`MODE_VAR`], falling back to color on terminals
+    /// that do not ask for `NO_COLOR`.
+    #[must_use]
+    pub fn from_env() -> Self {
+        let mode = match env::var(MODE_VAR).as_deref() {
+            Ok("color") => Mode::Color,
+            Ok("plain") => Mode::Plain,
+            Ok("off") => Mode::Off,
+            _ if env::var_os("NO_COLOR").is_none() && io::stdout().is_terminal() => Mode::Color,
+            _ => Mode::Plain,
+        };
+        Self::new(mode)
+    }
+
+    #[must_use]
+    pub fn is_enabled(self) -> bool {
+        self.mode != Mode::Off
+    }
+
+    /// Draws `canvases` side by side, separated by ` | `, under their
+    /// titles. Each legend entry shows a cell and what it stands for.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error from writing to `out`.
+    pub fn draw(
+        self,
+        out: &mut impl Write,
+        canvases: &[&Canvas],
+        legend: &[(Cell, &str)],
+    ) -> io::Result<()> {
+        if !self.is_enabled() {
+            return Ok(());
+        }
+
+        let widths = canvases
+            .iter()
+            .map(|canvas| canvas.width().max(canvas.title.chars().count()))
+            .collect::<Vec<_>>();
+
+        if canvases.iter().any(|canvas| !canvas.title.is_empty()) {
+            let titles = canvases
+                .iter()
+                .zip(&widths)
+                .map(|(canvas, &width)| format!("{:<width$}", canvas.title))
+                .collect::<Vec<_>>();
+            writeln!(out, "{}", titles.join(" | ").trim_end())?;
+            let separators = widths.len().saturating_sub(1) * 3;
+            writeln!(
+                out,
+                "{}",
+                "-".repeat(widths.iter().sum::<usize>() + separators)
+            )?;
+        }
+
+        let height = canvases
+            .iter()
+            .map(|canvas| canvas.height())
+            .max()
+            .unwrap_or_default();
+        for y in 0..height {
+            let mut line = String::new();
+            for (index, (canvas, &width)) in canvases.iter().zip(&widths).enumerate() {
+                if index > 0 {
+                    line.push_str(" | ");
+                }
+                let row = canvas.row(y);
+                self.push_cells(&mut line, row);
+                line.push_str(&" ".repeat(width - row.len()));
+            }
+            writeln!(out, "{}", line.trim_end())?;
+        }
+
+        if !legend.is_empty() {
+            let mut line = String::new();
+            for (index, &(cell, label)) in legend.iter().enumerate() {
+                if index > 0 {
+                    line.push_str("  ");
+                }
+                self.push_cells(&mut line, &[cell]);
+                line.push(' ');
+                line.push_str(label);
+            }
+            writeln!(out, "{line}")?;
+        }
+
+        Ok(())
+    }
+
+    /// Appends `cells`, switching styles only where they change and
+    /// resetting the style afterwards.
+    fn push_cells(self, line: &mut String, cells: &[Cell]) {
+        let mut current = Style::PLAIN;
+        for cell in cells {
+            if self.mode == Mode::Color && cell.style != current {
+                line.push_str(&cell.style.escape());
+                current = cell.style;
+            }
+            line.push(cell.glyph);
+        }
+        if current != Style::PLAIN {
+            line.push_str(&Style::PLAIN.escape());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn draw(renderer: Renderer, canvases: &[&Canvas], legend: &[(Cell, &str)]) -> String {
+        let mut out = Vec::new();
+        renderer
+            .draw(&mut out, canvases, legend)
+            .expect("writing to a vector");
+        String::from_utf8(out).expect("the output is text")
+    }
+
+    #[test]
+    fn test_canvas() {
+        let grid = Grid::<char>::parse("ab\ncd\n").expect("rectangular grid");
+        let mut canvas = Canvas::from_grid(&grid, |_, &tile| Cell::plain(tile));
+        canvas.mark([Point::new(1, 0), Point::new(5, 5)], Cell::plain('#'));
+        canvas.highlight([Point::new(0, 1)], Style::fg(Color::Red));
+
+        assert_eq!(canvas.get(Point::new(1, 0)), Some(&Cell::plain('#')));
+        assert_eq!(
+            canvas.get(Point::new(0, 1)),
+            Some(&Cell::new('c', Style::fg(Color::Red)))
+        );
+        assert_eq!(canvas.get(Point::new(1, 1)), Some(&Cell::plain('d')));
+
+        let cropped = canvas.crop(1, 5);
+        assert_eq!((cropped.width(), cropped.height()), (1, 2));
+        assert_eq!(cropped.row(1), [Cell::new('c', Style::fg(Color::Red))]);
+
+        let combined = canvas.beside(&Canvas::new(1, 3, Cell::plain('x')), 1);
+        assert_eq!((combined.width(), combined.height()), (4, 3));
+        let rows = (0..3)
+            .map(|y| {
+                combined
+                    .row(y)
+                    .iter()
+                    .map(|cell| cell.glyph)
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(rows, ["a# x", "cd x", "   x"]);
+    }
+
+    #[test]
+    fn test_side_by_side() {
+        let left = Canvas::new(3, 2, Cell::plain('.')).titled("Left");
+        let mut right = Canvas::new(2, 1, Cell::plain('.')).titled("Right side");
+        right.set(Point::ORIGIN, Cell::new('#', Style::fg(Color::Red)));
+
+        assert_eq!(
+            draw(
+                Renderer::new(Mode::Plain),
+                &[&left, &right],
+                &[(Cell::new('#', Style::fg(Color::Red)), "marker")]
+            ),
+            "Left | Right side\n-----------------\n...  | #.\n...  |\n# marker\n"
+        );
+        assert_eq!(draw(Renderer::new(Mode::Off), &[&left, &right], &[]), "");
+    }
+
+    #[test]
+    fn test_color() {
+        let mut canvas = Canvas::new(3, 1, Cell::plain('.'));
+        canvas.mark(
+            [Point::new(0, 0), Point::new(1, 0)],
+            Cell::new('#', Style::fg(Color::BrightRed).bold()),
+        );
+
+        assert_eq!(
+            draw(Renderer::new(Mode::Color), &[&canvas], &[]),
+            "\x1b[0;1;91m##\x1b[0m.\n"
+        );
+        assert_eq!(
+            Style::fg(Color::Black).on(Color::White).dim().escape(),
+            "\x1b[0;2;30;47m"
+        );
+    }
+}
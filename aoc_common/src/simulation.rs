@@ -0,0 +1,122 @@
+//! Simulations that can be inspected one step at a time.
+//!
+//! A day exposes a simulation as an iterator over its states, the first
+//! item being the state before anything happened. Stepping backwards is
+//! served by a [`History`], which keeps a copy of the iterator every few
+//! steps and replays from the nearest one, so neither the states nor the
+//! canvases of long simulations have to be kept around.
+
+use crate::render::Canvas;
+
+/// One state of a simulation.
+pub trait Frame {
+    /// The state drawn on a canvas.
+    fn canvas(&self) -> Canvas;
+
+    /// Named values describing the state, such as the number of moves.
+    fn counters(&self) -> Vec<(&'static str, String)>;
+}
+
+/// Random access to the items of a cloneable iterator.
+#[derive(Debug, Clone)]
+pub struct History<I> {
+    /// The iterator before items `0`, `interval`, `2 * interval` and so on.
+    checkpoints: Vec<I>,
+    interval: usize,
+    /// Number of items, once the iterator ran out.
+    len: Option<usize>,
+}
+
+impl<I: Iterator + Clone> History<I> {
+    /// Steps between two stored copies of the iterator.
+    pub const DEFAULT_INTERVAL: usize = 64;
+
+    #[must_use]
+    pub fn new(steps: I) -> Self {
+        Self::with_interval(steps, Self::DEFAULT_INTERVAL)
+    }
+
+    /// Stores a copy of the iterator every `interval` steps, trading memory
+    /// for the cost of replaying up to `interval` steps on every access.
+    #[must_use]
+    pub fn with_interval(steps: I, interval: usize) -> Self {
+        Self {
+            checkpoints: vec![steps],
+            interval: interval.max(1),
+            len: None,
+        }
+    }
+
+    /// Number of items, if the end has been reached.
+    #[must_use]
+    pub fn len(&self) -> Option<usize> {
+        self.len
+    }
+
+    /// Whether the iterator turned out to have no items at all.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len == Some(0)
+    }
+
+    /// Item `index`, or `None` past the end.
+    pub fn get(&mut self, index: usize) -> Option<I::Item> {
+        let checkpoint = index / self.interval;
+        while self.checkpoints.len() <= checkpoint {
+            let last = self.checkpoints.last()?;
+            let mut next = last.clone();
+            let start = (self.checkpoints.len() - 1) * self.interval;
+
+            let advanced = next.by_ref().take(self.interval).count();
+            if advanced < self.interval {
+                self.len = Some(start + advanced);
+                return None;
+            }
+            self.checkpoints.push(next);
+        }
+
+        let item = self
+            .checkpoints
+            .get(checkpoint)?
+            .clone()
+            .nth(index % self.interval);
+        if item.is_none() && self.len.is_none() {
+            self.len = Some(self.last_index(checkpoint));
+        }
+        item
+    }
+
+    /// Number of items counted from the start of `checkpoint`.
+    fn last_index(&self, checkpoint: usize) -> usize {
+        let start = checkpoint * self.interval;
+        self.checkpoints
+            .get(checkpoint)
+            .map_or(start, |steps| start + steps.clone().count())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_history() {
+        let mut history = History::with_interval(0..10, 3);
+        assert_eq!(history.get(4), Some(4));
+        assert_eq!(history.get(0), Some(0));
+        assert_eq!(history.len(), None);
+        assert_eq!(history.get(9), Some(9));
+        assert_eq!(history.get(10), None);
+        assert_eq!(history.len(), Some(10));
+        assert_eq!(history.get(25), None);
+        assert_eq!(history.get(7), Some(7));
+
+        let mut short = History::with_interval(0..4, 3);
+        assert_eq!(short.get(20), None);
+        assert_eq!(short.len(), Some(4));
+
+        let mut empty = History::new(0..0);
+        assert_eq!(empty.get(0), None);
+        assert!(empty.is_empty());
+    }
+}
@@ -0,0 +1,72 @@
+//! The interface every day implements.
+
+use crate::part::Part;
+use crate::render::Canvas;
+use std::error::Error;
+use std::fmt::Display;
+
+/// A puzzle solution split into parsing and the two parts.
+///
+/// The input is parsed once and then shared by both parts, so callers can
+/// time and run each phase separately.
+pub trait Solution {
+    /// Day of the puzzle in December.
+    const DAY: u8;
+    /// Title of the puzzle.
+    const TITLE: &'static str;
+
+    /// The parsed puzzle input.
+    type Input;
+    /// Why the puzzle input could not be parsed.
+    type Error: Error + Send + Sync + 'static;
+
+    /// Parses the raw puzzle input.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Self::Error`] if the input is malformed.
+    fn parse(input: &str) -> Result<Self::Input, Self::Error>;
+
+    fn part_one(input: &Self::Input) -> impl Display;
+
+    fn part_two(input: &Self::Input) -> impl Display;
+
+    /// Solves `part` and formats the answer.
+    fn solve(input: &Self::Input, part: Part) -> String {
+        match part {
+            Part::One => Self::part_one(input).to_string(),
+            Part::Two => Self::part_two(input).to_string(),
+        }
+    }
+
+    /// Other implementations of the parts, which have to give the same
+    /// answers as [`Self::solve`] on every input.
+    #[must_use]
+    fn variants() -> Vec<Variant<Self::Input>> {
+        Vec::new()
+    }
+
+    /// Pictures of the input or its solution, such as the guard's path, for
+    /// reports.
+    #[must_use]
+    fn canvases(_input: &Self::Input) -> Vec<Canvas> {
+        Vec::new()
+    }
+}
+
+/// Another way to solve one part of a day, such as a slow reference solver
+/// that is easier to trust than the real one.
+pub struct Variant<I> {
+    /// How the variant differs, such as `full grid`.
+    pub name: &'static str,
+    pub part: Part,
+    /// Solves the part and formats the answer like [`Solution::solve`].
+    pub solve: fn(&I) -> String,
+}
+
+impl<I> Variant<I> {
+    #[must_use]
+    pub const fn new(name: &'static str, part: Part, solve: fn(&I) -> String) -> Self {
+        Self { name, part, solve }
+    }
+}
@@ -0,0 +1,336 @@
+//! A terminal UI for stepping through a [simulation](crate::simulation).
+//!
+//! | Key                 | Action                              |
+//! |---------------------|-------------------------------------|
+//! | `→` `l` / `←` `h`   | next / previous step                |
+//! | `Home`              | first step                          |
+//! | `g`, digits, `Enter`| jump to a step, `Esc` cancels       |
+//! | `Space`             | play / pause                        |
+//! | `+` / `-`           | double / halve the playback speed   |
+//! | `q` `Esc`           | quit                                |
+
+use crate::render::{Mode, Renderer};
+use crate::simulation::{Frame, History};
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::style::Print;
+use crossterm::terminal::{self, Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen};
+use crossterm::{cursor, execute, queue};
+use std::io::{self, Write};
+use std::time::Duration;
+
+/// Lines around the canvas: the status above it, counters and keys below.
+const CHROME_LINES: usize = 5;
+const MAX_SPEED: u32 = 1024;
+
+/// What a key press asks for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Command {
+    Next,
+    Previous,
+    First,
+    TogglePlay,
+    Faster,
+    Slower,
+    /// Start typing a step to jump to.
+    Jump,
+    Digit(u8),
+    Backspace,
+    Confirm,
+    Cancel,
+    Quit,
+}
+
+impl Command {
+    /// The command bound to `key`, if any.
+    #[must_use]
+    pub fn from_key(key: KeyEvent) -> Option<Self> {
+        if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+            return Some(Self::Quit);
+        }
+        match key.code {
+            KeyCode::Right | KeyCode::Char('l' | 'n') => Some(Self::Next),
+            KeyCode::Left | KeyCode::Char('h' | 'p') => Some(Self::Previous),
+            KeyCode::Home => Some(Self::First),
+            KeyCode::Char(' ') => Some(Self::TogglePlay),
+            KeyCode::Char('+' | '=') => Some(Self::Faster),
+            KeyCode::Char('-') => Some(Self::Slower),
+            KeyCode::Char('g') => Some(Self::Jump),
+            KeyCode::Char(digit @ '0'..='9') => digit
+                .to_digit(10)
+                .and_then(|digit| u8::try_from(digit).ok())
+                .map(Self::Digit),
+            KeyCode::Backspace => Some(Self::Backspace),
+            KeyCode::Enter => Some(Self::Confirm),
+            KeyCode::Esc => Some(Self::Cancel),
+            KeyCode::Char('q') => Some(Self::Quit),
+            _ => None,
+        }
+    }
+}
+
+/// Which step is shown and how playback goes on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Player {
+    pub step: usize,
+    pub playing: bool,
+    /// Steps per second while playing.
+    pub speed: u32,
+    /// The step typed so far after [`Command::Jump`].
+    pub jump: Option<String>,
+    pub quit: bool,
+}
+
+impl Default for Player {
+    fn default() -> Self {
+        Self {
+            step: 0,
+            playing: false,
+            speed: 8,
+            jump: None,
+            quit: false,
+        }
+    }
+}
+
+impl Player {
+    /// Applies `command`. Moves past the end are undone by [`Self::clamp`]
+    /// once the number of steps is known.
+    pub fn handle(&mut self, command: Command) {
+        if let Some(jump) = &mut self.jump {
+            match command {
+                Command::Digit(digit) => jump.push(char::from(b'0' + digit)),
+                Command::Backspace => {
+                    jump.pop();
+                }
+                Command::Confirm => {
+                    self.step = jump.parse().unwrap_or(self.step);
+                    self.jump = None;
+                }
+                Command::Cancel | Command::Jump => self.jump = None,
+                Command::Quit => self.quit = true,
+                _ => {}
+            }
+            return;
+        }
+
+        match command {
+            Command::Next => self.step += 1,
+            Command::Previous => self.step = self.step.saturating_sub(1),
+            Command::First => self.step = 0,
+            Command::TogglePlay => self.playing = !self.playing,
+            Command::Faster => self.speed = (self.speed * 2).min(MAX_SPEED),
+            Command::Slower => self.speed = (self.speed / 2).max(1),
+            Command::Jump => self.jump = Some(String::new()),
+            Command::Quit | Command::Cancel => self.quit = true,
+            Command::Digit(_) | Command::Backspace | Command::Confirm => {}
+        }
+    }
+
+    /// Moves back to the last step of a simulation with `len` steps and
+    /// stops playing there.
+    pub fn clamp(&mut self, len: usize) {
+        if self.step >= len {
+            self.step = len.saturating_sub(1);
+            self.playing = false;
+        }
+    }
+
+    /// Time between two steps while playing.
+    #[must_use]
+    pub fn delay(&self) -> Duration {
+        Duration::from_secs(1) / self.speed.max(1)
+    }
+
+    /// The line above the canvas.
+    #[must_use]
+    pub fn status(&self, title: &str, len: Option<usize>) -> String {
+        let total = len.map_or_else(|| "?".to_owned(), |len| len.saturating_sub(1).to_string());
+        let playback = if self.playing { "playing" } else { "paused" };
+        let mut status = format!(
+            "{title}  step {}/{total}  {playback} at {} steps/s",
+            self.step, self.speed
+        );
+        if let Some(jump) = &self.jump {
+            status.push_str("  jump to: ");
+            status.push_str(jump);
+        }
+        status
+    }
+}
+
+/// Leaves the alternate screen and raw mode when dropped, also on errors.
+struct Screen<W: Write> {
+    out: W,
+}
+
+impl<W: Write> Screen<W> {
+    fn enter(mut out: W) -> io::Result<Self> {
+        terminal::enable_raw_mode()?;
+        execute!(out, EnterAlternateScreen, cursor::Hide)?;
+        Ok(Self { out })
+    }
+}
+
+impl<W: Write> Drop for Screen<W> {
+    fn drop(&mut self) {
+        // Nothing sensible is left to do if restoring the terminal fails
+        let restored = execute!(self.out, cursor::Show, LeaveAlternateScreen);
+        drop(restored.and_then(|()| terminal::disable_raw_mode()));
+    }
+}
+
+/// Shows the states of `steps` until the user quits.
+///
+/// # Errors
+///
+/// Returns any error from reading keys or writing to the terminal.
+pub fn run<I>(title: &str, steps: I) -> io::Result<()>
+where
+    I: Iterator + Clone,
+    I::Item: Frame,
+{
+    let mut history = History::new(steps);
+    let mut player = Player::default();
+    let mut screen = Screen::enter(io::stdout())?;
+
+    while !player.quit {
+        let frame = match history.get(player.step) {
+            Some(frame) => frame,
+            None if player.step == 0 => return Ok(()),
+            None => {
+                player.clamp(history.len().unwrap_or_default());
+                continue;
+            }
+        };
+        draw(&mut screen.out, &player, title, history.len(), &frame)?;
+
+        let timeout = if player.playing {
+            player.delay()
+        } else {
+            Duration::from_mins(1)
+        };
+        if event::poll(timeout)? {
+            if let Event::Key(key) = event::read()? {
+                if key.kind != KeyEventKind::Release {
+                    if let Some(command) = Command::from_key(key) {
+                        player.handle(command);
+                    }
+                }
+            }
+        } else if player.playing {
+            player.handle(Command::Next);
+        }
+    }
+
+    Ok(())
+}
+
+fn draw(
+    out: &mut impl Write,
+    player: &Player,
+    title: &str,
+    len: Option<usize>,
+    frame: &impl Frame,
+) -> io::Result<()> {
+    let (columns, rows) = terminal::size()?;
+    let (columns, rows) = (usize::from(columns), usize::from(rows));
+    let canvas = frame.canvas();
+    let visible = canvas.crop(columns, rows.saturating_sub(CHROME_LINES));
+
+    let mut drawn = Vec::new();
+    Renderer::new(Mode::Color).draw(&mut drawn, &[&visible], &[])?;
+    let drawn = String::from_utf8_lossy(&drawn);
+
+    let counters = frame
+        .counters()
+        .into_iter()
+        .map(|(name, value)| format!("{name}: {value}"))
+        .collect::<Vec<_>>()
+        .join("  ");
+    let cropped = if (visible.width(), visible.height()) == (canvas.width(), canvas.height()) {
+        ""
+    } else {
+        "  (cropped to the terminal)"
+    };
+
+    queue!(out, Clear(ClearType::All), cursor::MoveTo(0, 0))?;
+    let lines = [player.status(title, len)]
+        .into_iter()
+        .chain(drawn.lines().map(str::to_owned))
+        .chain([
+            format!("{counters}{cropped}"),
+            "→/← step  Home first  g jump  Space play/pause  +/- speed  q quit".to_owned(),
+        ]);
+    for line in lines {
+        queue!(out, Print(line), Print("\r\n"))?;
+    }
+    out.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_player() {
+        let mut player = Player::default();
+        for command in [
+            Command::Next,
+            Command::Next,
+            Command::Previous,
+            Command::Faster,
+        ] {
+            player.handle(command);
+        }
+        assert_eq!((player.step, player.speed), (1, 16));
+        assert_eq!(
+            player.delay(),
+            Duration::from_millis(62) + Duration::from_micros(500)
+        );
+
+        player.handle(Command::Jump);
+        for command in [
+            Command::Digit(4),
+            Command::Digit(7),
+            Command::Backspace,
+            Command::Digit(2),
+        ] {
+            player.handle(command);
+        }
+        assert_eq!(
+            player.status("Day 6", None),
+            "Day 6  step 1/?  paused at 16 steps/s  jump to: 42"
+        );
+        player.handle(Command::Confirm);
+        assert_eq!((player.step, player.jump.as_deref()), (42, None));
+
+        player.handle(Command::TogglePlay);
+        player.clamp(10);
+        assert_eq!((player.step, player.playing), (9, false));
+        assert_eq!(
+            player.status("Day 6", Some(10)),
+            "Day 6  step 9/9  paused at 16 steps/s"
+        );
+
+        player.handle(Command::Jump);
+        player.handle(Command::Cancel);
+        assert!(!player.quit);
+        player.handle(Command::Cancel);
+        assert!(player.quit);
+    }
+
+    #[test]
+    fn test_keys() {
+        let key = |code| KeyEvent::new(code, KeyModifiers::NONE);
+        assert_eq!(Command::from_key(key(KeyCode::Right)), Some(Command::Next));
+        assert_eq!(
+            Command::from_key(key(KeyCode::Char('7'))),
+            Some(Command::Digit(7))
+        );
+        assert_eq!(Command::from_key(key(KeyCode::Char('x'))), None);
+        assert_eq!(
+            Command::from_key(KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL)),
+            Some(Command::Quit)
+        );
+    }
+}
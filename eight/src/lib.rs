@@ -0,0 +1,189 @@
+//! Day 8: Resonant Collinearity.
+
+use aoc_common::generate::{Generate, Rng};
+use aoc_common::render::{Canvas, Cell, Color, Renderer, Style};
+use aoc_common::{input::Sample, Grid, GridError, Point, Solution};
+use rayon::prelude::*;
+use std::collections::{HashMap, HashSet};
+use std::fmt::Display;
+use std::io::{self, Write};
+
+/// The example from the puzzle description, read from `sample.txt`.
+pub const SAMPLE: Sample = aoc_common::sample!();
+
+/// The [`Solution`] of day 8.
+pub struct ResonantCollinearity;
+
+/// The antenna map together with the antenna positions per frequency.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AntennaMap {
+    grid: Grid<char>,
+    antennas: HashMap<char, Vec<Point>>,
+}
+
+impl Solution for ResonantCollinearity {
+    const DAY: u8 = 8;
+    const TITLE: &'static str = "Resonant Collinearity";
+
+    type Input = AntennaMap;
+    type Error = GridError;
+
+    fn parse(input: &str) -> Result<AntennaMap, GridError> {
+        let grid = Grid::parse(input)?;
+        let antennas = collect_antennas(&grid);
+
+        Ok(AntennaMap { grid, antennas })
+    }
+
+    /// Number of unique antinode locations under the first rule.
+    fn part_one(map: &AntennaMap) -> impl Display {
+        calculate_antinodes(&map.grid, &map.antennas, find_antinodes_rule1).len()
+    }
+
+    /// Number of unique antinode locations under the resonant harmonics
+    /// rule.
+    fn part_two(map: &AntennaMap) -> impl Display {
+        calculate_antinodes(&map.grid, &map.antennas, find_antinodes_rule2).len()
+    }
+
+    fn canvases(map: &AntennaMap) -> Vec<Canvas> {
+        antinode_canvases(map).into()
+    }
+}
+
+const ANTENNA: Style = Style::fg(Color::Cyan).bold();
+const ANTINODE: Cell = Cell::new('#', Style::fg(Color::Red).bold());
+/// An antinode on top of an antenna keeps the antenna's frequency visible.
+const ANTINODE_ANTENNA: Style = ANTENNA.on(Color::Red);
+
+/// Draws the map with the antinodes of both rules side by side.
+///
+/// # Errors
+///
+/// Returns any error from writing to `out`.
+pub fn draw_antinodes(
+    out: &mut impl Write,
+    renderer: Renderer,
+    map: &AntennaMap,
+) -> io::Result<()> {
+    let [rule1, rule2] = antinode_canvases(map);
+
+    renderer.draw(
+        out,
+        &[&rule1, &rule2],
+        &[
+            (Cell::new('A', ANTENNA), "antenna"),
+            (ANTINODE, "antinode"),
+            (Cell::new('A', ANTINODE_ANTENNA), "antinode on an antenna"),
+        ],
+    )
+}
+
+fn collect_antennas(grid: &Grid<char>) -> HashMap<char, Vec<Point>> {
+    let mut antennas = HashMap::<char, Vec<Point>>::new();
+    for (position, &cell) in grid.iter() {
+        if cell.is_alphanumeric() {
+            antennas.entry(cell).or_default().push(position);
+        }
+    }
+    antennas
+}
+
+fn calculate_antinodes<F>(
+    grid: &Grid<char>,
+    antennas: &HashMap<char, Vec<Point>>,
+    rule_fn: F,
+) -> HashSet<Point>
+where
+    F: Fn(&Grid<char>, &[Point]) -> HashSet<Point> + Sync,
+{
+    antennas
+        .par_iter()
+        .map(|(_, positions)| rule_fn(grid, positions))
+        .reduce(HashSet::new, |mut acc, antinodes| {
+            acc.extend(antinodes);
+            acc
+        })
+}
+
+/// The map with the antinodes of the first and of the second rule.
+#[must_use]
+pub fn antinode_canvases(map: &AntennaMap) -> [Canvas; 2] {
+    let rule1_antinodes = calculate_antinodes(&map.grid, &map.antennas, find_antinodes_rule1);
+    let rule2_antinodes = calculate_antinodes(&map.grid, &map.antennas, find_antinodes_rule2);
+
+    [
+        antinode_canvas(&map.grid, &rule1_antinodes).titled("Grid (Rule 1)"),
+        antinode_canvas(&map.grid, &rule2_antinodes).titled("Grid (Rule 2)"),
+    ]
+}
+
+fn antinode_canvas(grid: &Grid<char>, antinodes: &HashSet<Point>) -> Canvas {
+    Canvas::from_grid(grid, |position, &cell| {
+        match (cell, antinodes.contains(&position)) {
+            ('.', false) => Cell::new('.', Style::PLAIN.dim()),
+            ('.', true) => ANTINODE,
+            (antenna, false) => Cell::new(antenna, ANTENNA),
+            (antenna, true) => Cell::new(antenna, ANTINODE_ANTENNA),
+        }
+    })
+}
+
+fn find_antinodes_rule1(grid: &Grid<char>, positions: &[Point]) -> HashSet<Point> {
+    positions
+        .par_iter()
+        .enumerate()
+        .flat_map_iter(|(i, &first)| {
+            positions.iter().skip(i + 1).map(move |&second| {
+                let delta = first.vector_to(second);
+
+                let valid_antinode1 = grid.offset(first, -delta);
+                let valid_antinode2 = grid.offset(second, delta);
+
+                valid_antinode1.into_iter().chain(valid_antinode2)
+            })
+        })
+        .flatten_iter()
+        .collect()
+}
+
+fn find_antinodes_rule2(grid: &Grid<char>, positions: &[Point]) -> HashSet<Point> {
+    let mut antinodes = HashSet::new();
+
+    for &first in positions {
+        positions
+            .iter()
+            .filter(|&&position| position != first)
+            .for_each(|&second| {
+                // Collect antinodes along the direction from first to second
+                antinodes.extend(grid.ray(first, first.vector_to(second)));
+            });
+    }
+
+    antinodes
+}
+
+const FREQUENCIES: &[u8] = b"0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+impl Generate for ResonantCollinearity {
+    const SIZE: &'static str = "grid side";
+    const DEFAULT_SIZE: usize = 50;
+
+    /// A square map with an antenna on one in twelve positions, about four
+    /// antennas sharing each frequency.
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let antennas = size * size / 12;
+        let frequencies = FREQUENCIES
+            .get(..(antennas / 4).clamp(1, FREQUENCIES.len()))
+            .unwrap_or(FREQUENCIES);
+
+        let mut grid = Grid::new(size, size, '.');
+        for _ in 0..antennas {
+            let frequency = rng.pick(frequencies).map_or('0', |&byte| char::from(byte));
+            if let Some(cell) = grid.get_mut(rng.point(size, size)) {
+                *cell = frequency;
+            }
+        }
+        grid.to_string()
+    }
+}
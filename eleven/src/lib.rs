@@ -0,0 +1,198 @@
+//! Day 11: Plutonian Pebbles.
+
+use aoc_common::generate::{Generate, Rng};
+use aoc_common::input::Sample;
+use aoc_common::parse::{self, ParseError};
+use aoc_common::{Part, Solution, Variant};
+use std::collections::HashMap;
+use std::fmt::Display;
+
+/// The example from the puzzle description, read from `sample.txt`.
+pub const SAMPLE: Sample = aoc_common::sample!();
+
+/// The [`Solution`] of day 11.
+pub struct PlutonianPebbles;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct Stone {
+    value: Vec<u8>,
+}
+
+impl Stone {
+    fn from_u64(n: u64) -> Self {
+        let value = if n == 0 {
+            vec![0]
+        } else {
+            n.to_string()
+                .chars()
+                .map(|char| char as u8 - b'0')
+                .collect()
+        };
+        Stone { value }
+    }
+
+    fn to_u64(&self) -> u64 {
+        self.value
+            .iter()
+            .fold(0, |acc, &digit| acc * 10 + u64::from(digit))
+    }
+
+    /// The stones this one turns into, or `None` if its number overflows.
+    fn blink(&self) -> Option<Vec<Stone>> {
+        let number = self.to_u64();
+        let digits = self.value.len();
+
+        Some(if number == 0 {
+            vec![Stone::from_u64(1)]
+        } else if digits.is_multiple_of(2) {
+            let (left, right) = self.value.split_at(digits / 2);
+            let left = left
+                .iter()
+                .fold(0, |acc, &digit| acc * 10 + u64::from(digit));
+            let right = right
+                .iter()
+                .fold(0, |acc, &digit| acc * 10 + u64::from(digit));
+            vec![Stone::from_u64(left), Stone::from_u64(right)]
+        } else {
+            vec![Stone::from_u64(number.checked_mul(2024)?)]
+        })
+    }
+}
+
+fn blink_stones(stones: HashMap<Stone, u64>, times: u64) -> Option<HashMap<Stone, u64>> {
+    (0..times).try_fold(stones, |current, _| {
+        current
+            .into_iter()
+            .try_fold(HashMap::new(), |mut acc, (stone, count)| {
+                for new_stone in stone.blink()? {
+                    *acc.entry(new_stone).or_insert(0) += count;
+                }
+                Some(acc)
+            })
+    })
+}
+
+/// Number of stones after blinking `times` at stones engraved with
+/// `numbers`, or `None` if a stone's number overflows a `u64`.
+#[must_use]
+pub fn count_stones(numbers: &[u64], times: u64) -> Option<u64> {
+    let initial_stones = numbers.iter().map(|&number| Stone::from_u64(number)).fold(
+        HashMap::new(),
+        |mut acc, stone| {
+            *acc.entry(stone).or_insert(0) += 1;
+            acc
+        },
+    );
+
+    blink_stones(initial_stones, times).map(|stones| stones.values().sum())
+}
+
+/// Blinks by keeping every single stone in a list.
+fn naive_count(numbers: &[u64], times: u64) -> Option<usize> {
+    let mut stones = numbers.to_vec();
+    for _ in 0..times {
+        stones = stones
+            .into_iter()
+            .map(|stone| {
+                let digits = stone.to_string();
+                Some(if stone == 0 {
+                    vec![1]
+                } else if digits.len().is_multiple_of(2) {
+                    let (left, right) = digits.split_at(digits.len() / 2);
+                    vec![
+                        left.parse().expect("a number"),
+                        right.parse().expect("a number"),
+                    ]
+                } else {
+                    vec![stone.checked_mul(2024)?]
+                })
+            })
+            .collect::<Option<Vec<_>>>()?
+            .concat();
+    }
+    Some(stones.len())
+}
+
+impl Solution for PlutonianPebbles {
+    const DAY: u8 = 11;
+    const TITLE: &'static str = "Plutonian Pebbles";
+
+    /// The numbers engraved on the stones.
+    type Input = Vec<u64>;
+    type Error = ParseError;
+
+    fn parse(input: &str) -> Result<Vec<u64>, ParseError> {
+        parse::lines(input)
+            .flat_map(|line| line.split_whitespace())
+            .map(|number| number.parse())
+            .collect()
+    }
+
+    /// Number of stones after blinking 25 times.
+    fn part_one(numbers: &Vec<u64>) -> impl Display {
+        answer(count_stones(numbers, 25))
+    }
+
+    /// Number of stones after blinking 75 times.
+    fn part_two(numbers: &Vec<u64>) -> impl Display {
+        answer(count_stones(numbers, 75))
+    }
+
+    fn variants() -> Vec<Variant<Vec<u64>>> {
+        vec![Variant::new("every stone", Part::One, |numbers| {
+            answer(naive_count(numbers, 25))
+        })]
+    }
+}
+
+/// The number of stones, or `overflow` if they could not be counted.
+fn answer(count: Option<impl Display>) -> String {
+    count.map_or_else(|| "overflow".to_owned(), |count| count.to_string())
+}
+
+impl Generate for PlutonianPebbles {
+    const SIZE: &'static str = "stones";
+    const DEFAULT_SIZE: usize = 8;
+
+    /// Stones engraved with numbers of one to seven digits.
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let stones = (0..size)
+            .map(|_| {
+                let digits = u32::try_from(rng.below(7) + 1).unwrap_or(1);
+                rng.range(0..=10_i64.pow(digits) - 1).to_string()
+            })
+            .collect::<Vec<_>>();
+        format!("{}\n", stones.join(" "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn test_count_matches_naive_simulation(
+            numbers in prop::collection::vec(0..100_000_u64, 1..5),
+            times in 0..=12_u64,
+        ) {
+            prop_assert_eq!(
+                count_stones(&numbers, times),
+                naive_count(&numbers, times)
+                    .map(|count| u64::try_from(count).expect("few stones"))
+            );
+        }
+    }
+
+    #[test]
+    fn test_overflow() {
+        let numbers = [u64::MAX / 1000];
+        assert_eq!(count_stones(&numbers, 1), None);
+        assert_eq!(naive_count(&numbers, 1), None);
+        assert_eq!(
+            PlutonianPebbles::part_one(&numbers.to_vec()).to_string(),
+            "overflow"
+        );
+    }
+}
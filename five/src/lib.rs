@@ -0,0 +1,237 @@
+//! Day 5: Print Queue.
+
+use aoc_common::generate::{Generate, Rng};
+use aoc_common::input::Sample;
+use aoc_common::parse::{self, ParseError, Token};
+use aoc_common::Solution;
+use std::collections::{HashMap, HashSet};
+use std::fmt::Display;
+
+/// The example from the puzzle description, read from `sample.txt`.
+pub const SAMPLE: Sample = aoc_common::sample!();
+
+/// The [`Solution`] of day 5.
+pub struct PrintQueue;
+
+type Rules = HashMap<u32, HashSet<u32>>;
+
+/// The page ordering rules and the updates to check against them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Manual {
+    rules: Rules,
+    orders: Vec<Vec<u32>>,
+}
+
+impl Solution for PrintQueue {
+    const DAY: u8 = 5;
+    const TITLE: &'static str = "Print Queue";
+
+    type Input = Manual;
+    type Error = ParseError;
+
+    fn parse(input: &str) -> Result<Manual, ParseError> {
+        let mut sections = parse::paragraphs(input);
+        let rule_lines = sections.next().unwrap_or_default();
+        let order_lines = sections.next().unwrap_or_default();
+        if let Some(line) = sections.next().and_then(|section| section.first().copied()) {
+            return Err(line.error("expected only the rules and the updates"));
+        }
+
+        let mut rules = HashMap::new();
+        for line in rule_lines {
+            let (before, after) = line.split_once('|')?;
+            rules
+                .entry(before.trim().parse::<u32>()?)
+                .or_insert_with(HashSet::new)
+                .insert(after.trim().parse::<u32>()?);
+        }
+
+        let orders = order_lines
+            .into_iter()
+            .map(parse_order)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Manual { rules, orders })
+    }
+
+    /// Sum of the middle page numbers of the correctly ordered updates.
+    fn part_one(manual: &Manual) -> impl Display {
+        manual
+            .orders
+            .iter()
+            .filter(|order| is_correct_order(order, &manual.rules))
+            .map(|order| get_middle(order))
+            .sum::<u32>()
+    }
+
+    /// Sum of the middle page numbers of the incorrectly ordered updates
+    /// after putting them in the right order.
+    fn part_two(manual: &Manual) -> impl Display {
+        manual
+            .orders
+            .iter()
+            .filter(|order| !is_correct_order(order, &manual.rules))
+            .map(|order| correct_order(order, &manual.rules))
+            .map(|corrected_order| get_middle(&corrected_order))
+            .sum::<u32>()
+    }
+}
+
+/// Parses an update, which needs a middle page to contribute to the answer.
+fn parse_order(line: Token<'_>) -> Result<Vec<u32>, ParseError> {
+    let pages = line
+        .split(',')
+        .map(|page| page.trim().parse::<u32>())
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if pages.len().is_multiple_of(2) {
+        return Err(line.error(format!(
+            "expected an odd number of pages but found {}",
+            pages.len()
+        )));
+    }
+
+    Ok(pages)
+}
+
+fn is_correct_order(order: &[u32], rules: &Rules) -> bool {
+    let index_map = order
+        .iter()
+        .enumerate()
+        .map(|(i, value)| (value, i))
+        .collect::<HashMap<_, _>>();
+
+    rules.iter().all(|(before, after_set)| {
+        if let Some(&before_index) = index_map.get(before) {
+            after_set.iter().all(|&after| {
+                index_map
+                    .get(&after)
+                    .is_none_or(|&after_index| before_index <= after_index)
+            })
+        } else {
+            true
+        }
+    })
+}
+
+fn get_middle(order: &[u32]) -> u32 {
+    let len = order.len();
+    *order
+        .get(len / 2)
+        .expect("parsing rejects updates without a middle page")
+}
+
+fn correct_order(order: &[u32], rules: &Rules) -> Vec<u32> {
+    let mut corrected_orders = order.to_vec();
+    let mut sorted = true;
+
+    while sorted {
+        sorted = false;
+        for i in 0..corrected_orders.len() {
+            if let Some(after_set) = corrected_orders.get(i).and_then(|page| rules.get(page)) {
+                for after in after_set {
+                    if let Some(after_index) = corrected_orders.iter().position(|x| x == after) {
+                        if after_index < i {
+                            corrected_orders.swap(i, after_index);
+                            sorted = true;
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    corrected_orders
+}
+
+/// Number of distinct pages in a generated manual.
+const GENERATED_PAGES: usize = 49;
+
+impl Generate for PrintQueue {
+    const SIZE: &'static str = "updates";
+    const DEFAULT_SIZE: usize = 200;
+
+    /// Rules for every pair of 49 two-digit pages, all following one hidden
+    /// order so they never contradict each other, and updates of five to
+    /// 23 pages of which about half are already in order.
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let mut order = (10..100).collect::<Vec<u32>>();
+        rng.shuffle(&mut order);
+        order.truncate(GENERATED_PAGES);
+
+        let mut rules = Vec::new();
+        for (index, before) in order.iter().enumerate() {
+            for after in order.iter().skip(index + 1) {
+                rules.push(format!("{before}|{after}\n"));
+            }
+        }
+        rng.shuffle(&mut rules);
+
+        let updates = (0..size).map(|_| {
+            let mut ranks = (0..order.len()).collect::<Vec<_>>();
+            rng.shuffle(&mut ranks);
+            ranks.truncate(rng.below(10) * 2 + 5);
+            if rng.chance(50) {
+                ranks.sort_unstable();
+            }
+            let pages = ranks
+                .iter()
+                .filter_map(|&rank| order.get(rank).map(u32::to_string))
+                .collect::<Vec<_>>();
+            format!("{}\n", pages.join(","))
+        });
+
+        format!(
+            "{}\n{}",
+            rules.concat(),
+            updates.collect::<Vec<_>>().concat()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    const PAGES: usize = 15;
+
+    /// Rules for some pairs of pages, all following one hidden order, and
+    /// an update of an odd number of the pages in any order.
+    fn rules_and_update() -> impl Strategy<Value = (Rules, Vec<u32>)> {
+        let order = Just((10..100).collect::<Vec<u32>>())
+            .prop_shuffle()
+            .prop_map(|order| order.into_iter().take(PAGES).collect::<Vec<_>>());
+
+        (order, 0..=PAGES / 2)
+            .prop_flat_map(|(order, half)| {
+                let pairs = prop::collection::vec(any::<bool>(), PAGES * PAGES);
+                let update = prop::sample::subsequence(order.clone(), half * 2 + 1).prop_shuffle();
+                (Just(order), pairs, update)
+            })
+            .prop_map(|(order, pairs, update)| {
+                let mut rules = Rules::new();
+                for (index, before) in order.iter().enumerate() {
+                    for (offset, after) in order.iter().enumerate().skip(index + 1) {
+                        if pairs.get(index * PAGES + offset) == Some(&true) {
+                            rules.entry(*before).or_default().insert(*after);
+                        }
+                    }
+                }
+                (rules, update)
+            })
+    }
+
+    proptest! {
+        #[test]
+        fn test_corrected_order_satisfies_rules((rules, update) in rules_and_update()) {
+            let corrected = correct_order(&update, &rules);
+            prop_assert!(is_correct_order(&corrected, &rules));
+
+            let (mut pages, mut corrected_pages) = (update, corrected);
+            pages.sort_unstable();
+            corrected_pages.sort_unstable();
+            prop_assert_eq!(pages, corrected_pages);
+        }
+    }
+}
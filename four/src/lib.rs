@@ -0,0 +1,215 @@
+//! Day 4: Ceres Search.
+
+use aoc_common::generate::{Generate, Rng};
+use aoc_common::{input::Sample, Grid, GridError, Point, Solution};
+use std::fmt::Display;
+
+/// The example from the puzzle description, read from `sample.txt`.
+pub const SAMPLE: Sample = aoc_common::sample!();
+
+/// The [`Solution`] of day 4.
+pub struct CeresSearch;
+
+impl Solution for CeresSearch {
+    const DAY: u8 = 4;
+    const TITLE: &'static str = "Ceres Search";
+
+    type Input = Grid<char>;
+    type Error = GridError;
+
+    fn parse(input: &str) -> Result<Grid<char>, GridError> {
+        Grid::parse(input)
+    }
+
+    /// Number of times `XMAS` appears in any direction.
+    fn part_one(grid: &Grid<char>) -> impl Display {
+        count_word(grid, "XMAS")
+    }
+
+    /// Number of `MAS` crosses.
+    fn part_two(grid: &Grid<char>) -> impl Display {
+        count_x_shape(grid)
+    }
+}
+
+/// Number of times `word` appears in a row, column or diagonal of `grid`,
+/// forwards or backwards.
+#[must_use]
+pub fn count_word(grid: &Grid<char>, word: &str) -> usize {
+    let horizontal_count = count_horizontal(grid, word);
+    let vertical_count = count_vertical(grid, word);
+    let diagonal_count = count_diagonal(grid, word);
+    let reverse_count = count_reverse(grid, word);
+    horizontal_count + vertical_count + diagonal_count + reverse_count
+}
+
+fn count_matches(lines: impl Iterator<Item = String>, word: &str) -> usize {
+    lines.map(|line| line.matches(word).count()).sum()
+}
+
+fn count_horizontal(grid: &Grid<char>, word: &str) -> usize {
+    count_matches(grid.rows().map(String::from_iter), word)
+}
+
+fn count_vertical(grid: &Grid<char>, word: &str) -> usize {
+    count_matches(grid.columns().map(String::from_iter), word)
+}
+
+fn count_diagonal(grid: &Grid<char>, word: &str) -> usize {
+    let diagonals = grid.diagonals().map(String::from_iter);
+    let anti_diagonals = grid.anti_diagonals().map(String::from_iter);
+    count_matches(diagonals.chain(anti_diagonals), word)
+}
+
+fn count_reverse(grid: &Grid<char>, word: &str) -> usize {
+    let reversed_word: String = word.chars().rev().collect();
+    let horizontal_count = count_horizontal(grid, &reversed_word);
+    let vertical_count = count_vertical(grid, &reversed_word);
+    let diagonal_count = count_diagonal(grid, &reversed_word);
+
+    horizontal_count + vertical_count + diagonal_count
+}
+
+/// Number of pairs of `MAS` that cross on their `A` diagonally, each read
+/// in either direction.
+#[must_use]
+pub fn count_x_shape(grid: &Grid<char>) -> usize {
+    grid.positions()
+        .map(|position| check_x_shape(grid, position))
+        .sum()
+}
+
+fn check_x_shape(grid: &Grid<char>, corner: Point) -> usize {
+    let mut count = 0;
+
+    let tile = |dx: usize, dy: usize| grid.get(Point::new(corner.x + dx, corner.y + dy)).copied();
+    let (Some(top_left), Some(center), Some(bottom_left), Some(top_right), Some(bottom_right)) =
+        (tile(0, 0), tile(1, 1), tile(0, 2), tile(2, 0), tile(2, 2))
+    else {
+        return 0;
+    };
+
+    //   M . S
+    //   . A .
+    //   M . S
+    if top_left == 'M'
+        && center == 'A'
+        && bottom_left == 'M'
+        && top_right == 'S'
+        && bottom_right == 'S'
+    {
+        count += 1;
+    }
+
+    //   S . M
+    //   . A .
+    //   S . M
+    if top_left == 'S'
+        && center == 'A'
+        && bottom_left == 'S'
+        && top_right == 'M'
+        && bottom_right == 'M'
+    {
+        count += 1;
+    }
+
+    //   M . M
+    //   . A .
+    //   S . S
+    if top_left == 'M'
+        && center == 'A'
+        && bottom_left == 'S'
+        && top_right == 'M'
+        && bottom_right == 'S'
+    {
+        count += 1;
+    }
+
+    //   S . S
+    //   . A .
+    //   M . M
+    if top_left == 'S'
+        && center == 'A'
+        && bottom_left == 'M'
+        && top_right == 'S'
+        && bottom_right == 'M'
+    {
+        count += 1;
+    }
+
+    count
+}
+
+impl Generate for CeresSearch {
+    const SIZE: &'static str = "grid side";
+    const DEFAULT_SIZE: usize = 140;
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let mut grid = Grid::new(size, size, 'X');
+        for position in grid.positions().collect::<Vec<_>>() {
+            if let Some(cell) = grid.get_mut(position) {
+                *cell = *rng.pick(&['X', 'M', 'A', 'S']).unwrap_or(&'X');
+            }
+        }
+        grid.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid(rows: Vec<Vec<char>>) -> Grid<char> {
+        Grid::try_from(rows).expect("rectangular grid")
+    }
+
+    #[test]
+    fn test_count_x_shape() {
+        let grid0 = grid(vec![
+            vec!['M', ' ', 'M'],
+            vec![' ', 'A', ' '],
+            vec!['S', ' ', 'S'],
+        ]);
+        assert_eq!(count_x_shape(&grid0), 1);
+
+        let grid1 = grid(vec![
+            vec!['M', ' ', 'M'],
+            vec![' ', 'A', ' '],
+            vec!['S', ' ', 'S'],
+            vec!['S', ' ', 'M'],
+            vec![' ', 'A', ' '],
+            vec!['S', ' ', 'M'],
+        ]);
+        assert_eq!(count_x_shape(&grid1), 2);
+
+        let grid2 = grid(vec![
+            vec!['S', ' ', 'S'],
+            vec![' ', 'A', ' '],
+            vec!['M', ' ', 'M'],
+            vec!['S', ' ', 'S'],
+            vec![' ', 'A', ' '],
+            vec!['M', ' ', 'M'],
+        ]);
+        assert_eq!(count_x_shape(&grid2), 2);
+
+        let grid3 = grid(vec![
+            vec!['M', ' ', 'S'],
+            vec![' ', 'A', ' '],
+            vec!['M', ' ', 'S'],
+            vec!['S', ' ', 'M'],
+            vec![' ', 'A', ' '],
+            vec!['S', ' ', 'M'],
+        ]);
+        assert_eq!(count_x_shape(&grid3), 2);
+
+        let grid4 = grid(vec![
+            vec!['M', ' ', 'S', 'M', ' ', 'S'],
+            vec![' ', 'A', ' ', ' ', 'A', ' '],
+            vec!['M', ' ', 'S', 'M', ' ', 'S'],
+            vec!['S', ' ', 'M', 'S', ' ', 'S'],
+            vec![' ', 'A', ' ', 'A', 'A', ' '],
+            vec!['S', ' ', 'M', 'M', ' ', 'M'],
+        ]);
+        assert_eq!(count_x_shape(&grid4), 4);
+    }
+}
@@ -0,0 +1,402 @@
+//! Day 14: Restroom Redoubt.
+
+use aoc_common::generate::{Generate, Rng};
+use aoc_common::parse::{self, ParseError, Token};
+use aoc_common::render::{Canvas, Cell, Color, Renderer, Style};
+use aoc_common::simulation::Frame;
+use aoc_common::{input::Sample, Direction8, Part, Point, Solution, Variant, Vector};
+use std::collections::HashSet;
+use std::fmt::Display;
+use std::io::{self, Write};
+
+/// The example from the puzzle description, read from `sample.txt`.
+pub const SAMPLE: Sample = aoc_common::sample!();
+
+/// The [`Solution`] of day 14.
+pub struct RestroomRedoubt;
+
+/// Size of the lobby in the puzzle input.
+const GRID_SIZE: (usize, usize) = (101, 103);
+/// Size of the lobby in the example, which the sample robots fit into.
+const SAMPLE_GRID_SIZE: (usize, usize) = (11, 7);
+const SIMULATION_DURATION: i32 = 100;
+/// Spread above which the robots form the tree: it shows up once almost no
+/// two robots share a position.
+const SPREAD_THRESHOLD: f32 = 0.9995;
+
+/// A robot's position and the distance it moves every second.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct Robot {
+    position: Point,
+    velocity: Vector,
+}
+
+impl Robot {
+    /// A robot at `position` that moves by `velocity` every second.
+    #[must_use]
+    pub fn new(position: Point, velocity: Vector) -> Self {
+        Self { position, velocity }
+    }
+
+    fn move_robot(&mut self, width: usize, height: usize) {
+        self.position = self.position.wrapping_add(self.velocity, width, height);
+    }
+}
+
+/// The robots together with the size of the lobby they patrol.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Lobby {
+    robots: Vec<Robot>,
+    width: usize,
+    height: usize,
+}
+
+impl Lobby {
+    /// Places `robots` in the example lobby if they all fit into it, and in
+    /// the full-size lobby otherwise.
+    #[must_use]
+    pub fn new(robots: Vec<Robot>) -> Self {
+        let (sample_width, sample_height) = SAMPLE_GRID_SIZE;
+        let fits_sample = robots
+            .iter()
+            .all(|robot| robot.position.x < sample_width && robot.position.y < sample_height);
+        let (width, height) = if fits_sample {
+            SAMPLE_GRID_SIZE
+        } else {
+            GRID_SIZE
+        };
+
+        Self {
+            robots,
+            width,
+            height,
+        }
+    }
+
+    /// Moves every robot by its velocity, wrapping around the lobby's
+    /// edges.
+    pub fn step(&mut self) {
+        for robot in &mut self.robots {
+            robot.move_robot(self.width, self.height);
+        }
+    }
+
+    /// Positions with at least one robot on them.
+    #[must_use]
+    pub fn robot_positions(&self) -> HashSet<Point> {
+        self.robots.iter().map(|robot| robot.position).collect()
+    }
+
+    /// Product of the numbers of robots in each quadrant, leaving out the
+    /// robots on the middle lines.
+    #[must_use]
+    pub fn safety_factor(&self) -> usize {
+        let (middle_x, middle_y) = (self.width / 2, self.height / 2);
+        let mut quadrant_counts = [0, 0, 0, 0];
+        for robot in &self.robots {
+            let Point { x, y } = robot.position;
+            if x == middle_x || y == middle_y {
+                continue;
+            }
+
+            let quadrant_index = if x < middle_x {
+                if y < middle_y {
+                    0 // Top-left
+                } else {
+                    2 // Bottom-left
+                }
+            } else if y < middle_y {
+                1 // Top-right
+            } else {
+                3 // Bottom-right
+            };
+
+            if let Some(count) = quadrant_counts.get_mut(quadrant_index) {
+                *count += 1;
+            }
+        }
+
+        quadrant_counts.iter().product()
+    }
+
+    /// Share of robots with another robot on one of the eight positions
+    /// around them.
+    #[expect(
+        clippy::cast_precision_loss,
+        reason = "there are far fewer robots than f32 represents exactly"
+    )]
+    fn adjacency(&self) -> f32 {
+        let positions = self.robot_positions();
+        let adjacent = self
+            .robots
+            .iter()
+            .filter(|robot| {
+                Direction8::ALL.into_iter().any(|direction| {
+                    let neighbour =
+                        robot
+                            .position
+                            .wrapping_add(direction.vector(), self.width, self.height);
+                    positions.contains(&neighbour)
+                })
+            })
+            .count();
+        adjacent as f32 / self.robots.len() as f32
+    }
+
+    /// Occupied positions per robot, which is 1 when no two robots share a
+    /// position.
+    #[expect(
+        clippy::cast_precision_loss,
+        reason = "there are far fewer robots than f32 represents exactly"
+    )]
+    fn spread(&self) -> f32 {
+        self.robot_positions().len() as f32 / self.robots.len() as f32
+    }
+
+    fn canvas(&self, seconds: usize) -> Canvas {
+        let mut canvas = Canvas::new(self.width, self.height, Cell::new('.', Style::PLAIN.dim()))
+            .titled(format!("After {seconds} seconds"));
+        canvas.mark(self.robot_positions(), ROBOT);
+        canvas
+    }
+}
+
+/// The robots moving one second at a time, from their starting positions
+/// until they are back there.
+#[derive(Debug, Clone)]
+pub struct RobotMotion {
+    lobby: Lobby,
+    seconds: usize,
+}
+
+impl RobotMotion {
+    /// The motion of the robots of `lobby` from where they start.
+    #[must_use]
+    pub fn new(lobby: &Lobby) -> Self {
+        Self {
+            lobby: lobby.clone(),
+            seconds: 0,
+        }
+    }
+}
+
+impl Iterator for RobotMotion {
+    type Item = LobbyState;
+
+    fn next(&mut self) -> Option<LobbyState> {
+        if self.seconds >= self.lobby.width * self.lobby.height {
+            return None;
+        }
+
+        let state = LobbyState {
+            lobby: self.lobby.clone(),
+            seconds: self.seconds,
+        };
+        self.lobby.step();
+        self.seconds += 1;
+        Some(state)
+    }
+}
+
+/// The lobby after the robots moved for a number of seconds.
+#[derive(Debug, Clone)]
+pub struct LobbyState {
+    lobby: Lobby,
+    seconds: usize,
+}
+
+impl Frame for LobbyState {
+    fn canvas(&self) -> Canvas {
+        self.lobby.canvas(self.seconds)
+    }
+
+    fn counters(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("seconds", self.seconds.to_string()),
+            ("safety factor", self.lobby.safety_factor().to_string()),
+            (
+                "adjacent robots",
+                format!("{:.1}%", self.lobby.adjacency() * 100.0),
+            ),
+            ("spread", format!("{:.1}%", self.lobby.spread() * 100.0)),
+        ]
+    }
+}
+
+const ROBOT: Cell = Cell::new('*', Style::fg(Color::Green).bold());
+
+/// Draws the lobby after the robots moved for `seconds`.
+///
+/// # Errors
+///
+/// Returns any error from writing to `out`.
+pub fn draw_lobby(
+    out: &mut impl Write,
+    renderer: Renderer,
+    lobby: &Lobby,
+    seconds: usize,
+) -> io::Result<()> {
+    renderer.draw(out, &[&lobby_canvas(lobby, seconds)], &[(ROBOT, "robot")])
+}
+
+/// The lobby after the robots moved for `seconds`.
+#[must_use]
+pub fn lobby_canvas(lobby: &Lobby, seconds: usize) -> Canvas {
+    let mut lobby = lobby.clone();
+    for _ in 0..seconds {
+        lobby.step();
+    }
+
+    lobby.canvas(seconds)
+}
+
+/// Parses a line like `p=0,4 v=3,-3`.
+fn parse_robot(line: Token<'_>) -> Result<Robot, ParseError> {
+    let (position, velocity) = line.split_once(' ')?;
+    let (x, y) = position.strip_prefix("p=")?.split_once(',')?;
+    let (dx, dy) = velocity.trim().strip_prefix("v=")?.split_once(',')?;
+
+    Ok(Robot {
+        position: Point::new(x.parse()?, y.parse()?),
+        velocity: Vector::new(dx.parse()?, dy.parse()?),
+    })
+}
+
+impl Solution for RestroomRedoubt {
+    const DAY: u8 = 14;
+    const TITLE: &'static str = "Restroom Redoubt";
+
+    type Input = Lobby;
+    type Error = ParseError;
+
+    fn parse(input: &str) -> Result<Lobby, ParseError> {
+        let robots = parse::lines(input)
+            .map(parse_robot)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Lobby::new(robots))
+    }
+
+    /// Safety factor after the robots moved for 100 seconds.
+    fn part_one(lobby: &Lobby) -> impl Display {
+        let mut lobby = lobby.clone();
+
+        for _ in 0..SIMULATION_DURATION {
+            lobby.step();
+        }
+
+        lobby.safety_factor()
+    }
+
+    /// Number of seconds until the robots first arrange into the Christmas
+    /// tree, or `none` if they never do.
+    fn part_two(lobby: &Lobby) -> impl Display {
+        find_christmas_tree(lobby).map_or_else(|| "none".to_owned(), |steps| steps.to_string())
+    }
+
+    /// The lobby when the Christmas tree appears, if it does.
+    fn canvases(lobby: &Lobby) -> Vec<Canvas> {
+        find_christmas_tree(lobby)
+            .map(|steps| lobby_canvas(lobby, steps))
+            .into_iter()
+            .collect()
+    }
+
+    fn variants() -> Vec<Variant<Lobby>> {
+        vec![Variant::new("closed form", Part::One, |lobby| {
+            reference_safety_factor(lobby).to_string()
+        })]
+    }
+}
+
+/// Safety factor after moving every robot by 100 times its velocity at once
+/// instead of one second at a time.
+fn reference_safety_factor(lobby: &Lobby) -> usize {
+    let seconds = isize::try_from(SIMULATION_DURATION).unwrap_or_default();
+    let robots = lobby
+        .robots
+        .iter()
+        .map(|robot| Robot {
+            position: robot.position.wrapping_add(
+                Vector::new(robot.velocity.x * seconds, robot.velocity.y * seconds),
+                lobby.width,
+                lobby.height,
+            ),
+            velocity: robot.velocity,
+        })
+        .collect();
+
+    Lobby {
+        robots,
+        width: lobby.width,
+        height: lobby.height,
+    }
+    .safety_factor()
+}
+
+/// Number of seconds until the robots first arrange into the Christmas
+/// tree, if they ever do.
+#[must_use]
+pub fn find_christmas_tree(lobby: &Lobby) -> Option<usize> {
+    let mut lobby = lobby.clone();
+
+    // The robots are back at their starting positions after this many steps
+    let max_iterations = lobby.width * lobby.height;
+
+    for iteration in 0..max_iterations {
+        lobby.step();
+
+        if lobby.spread() >= SPREAD_THRESHOLD {
+            return Some(iteration + 1);
+        }
+    }
+
+    None
+}
+
+impl Generate for RestroomRedoubt {
+    const SIZE: &'static str = "robots";
+    const DEFAULT_SIZE: usize = 500;
+
+    /// Robots anywhere in the full-size lobby, moving up to 99 tiles along
+    /// each axis every second.
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let (width, height) = GRID_SIZE;
+        (0..size)
+            .map(|_| {
+                let position = rng.point(width, height);
+                format!(
+                    "p={},{} v={},{}\n",
+                    position.x,
+                    position.y,
+                    rng.range(-99..=99),
+                    rng.range(-99..=99)
+                )
+            })
+            .collect::<Vec<_>>()
+            .concat()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_adjacency() {
+        // Three robots on their own, two side by side and one on top of
+        // another, in the sample's 11 by 7 lobby
+        let lobby = RestroomRedoubt::parse(
+            "p=2,2 v=0,0\n\
+             p=6,2 v=0,0\n\
+             p=3,5 v=0,0\n\
+             p=8,4 v=0,0\n\
+             p=9,5 v=0,0\n\
+             p=2,2 v=0,0\n",
+        )
+        .expect("a valid lobby");
+
+        assert!((lobby.adjacency() - 2.0 / 6.0).abs() < f32::EPSILON);
+        assert!((lobby.spread() - 5.0 / 6.0).abs() < f32::EPSILON);
+    }
+}
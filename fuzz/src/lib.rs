@@ -0,0 +1,32 @@
+//! Fuzz targets for the parser of every day, run with
+//! [cargo-fuzz](https://github.com/rust-fuzz/cargo-fuzz) on nightly:
+//!
+//! ```sh
+//! cd fuzz
+//! cargo +nightly fuzz run nine
+//! ```
+//!
+//! Each target's corpus in `corpus/<day>` starts out with the day's sample.
+//! Refresh it after changing a sample:
+//!
+//! ```sh
+//! for day in corpus/*; do cp "../${day#corpus/}/sample.txt" "$day/"; done
+//! ```
+
+use aoc_common::input;
+use aoc_common::Solution;
+use std::str;
+
+/// Parses `data` the way the runner would see it, if it is text at all.
+///
+/// A parser may reject the input, but it has to do so with an error that
+/// can be displayed. Any panic is a crash for the fuzzer to report.
+pub fn parse<S: Solution>(data: &[u8]) {
+    let Ok(raw) = str::from_utf8(data) else {
+        return;
+    };
+
+    if let Err(error) = S::parse(&input::normalize(raw)) {
+        drop(error.to_string());
+    }
+}
@@ -0,0 +1,541 @@
+//! Day 9: Disk Fragmenter.
+
+use aoc_common::generate::{Generate, Rng};
+use aoc_common::input::Sample;
+use aoc_common::parse::{self, ParseError};
+use aoc_common::render::{Canvas, Cell, Color, Renderer, Style};
+use aoc_common::simulation::Frame;
+use aoc_common::{Part, Point, Solution, Variant};
+use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+use std::collections::{HashSet, VecDeque};
+use std::fmt::Display;
+use std::io::{self, Write};
+
+/// The example from the puzzle description, read from `sample.txt`.
+pub const SAMPLE: Sample = aoc_common::sample!();
+
+/// The [`Solution`] of day 9.
+pub struct DiskFragmenter;
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+struct File {
+    size: usize,
+    id: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Space {
+    size: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Block {
+    File(File),
+    Space(Space),
+}
+
+/// The blocks of the disk as described by the disk map.
+#[derive(Debug, Clone)]
+pub struct Disk {
+    blocks: VecDeque<Block>,
+}
+
+impl Disk {
+    /// Reads a disk map: the sizes of the files and of the free space
+    /// between them alternating as digits on a single line.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ParseError`] if the map is empty, has more than one line
+    /// or holds anything but digits.
+    pub fn new(disk_map: &str) -> Result<Self, ParseError> {
+        let mut lines = parse::lines(disk_map);
+        let line = lines
+            .next()
+            .ok_or_else(|| ParseError::unlocated("the disk map is empty"))?;
+        if let Some(next_line) = lines.next() {
+            return Err(next_line.error("expected the disk map on a single line"));
+        }
+
+        let blocks = line
+            .chars()
+            .enumerate()
+            .map(|(id, (location, char))| {
+                let size = char.to_digit(10).ok_or_else(|| {
+                    ParseError::new(location, format!("expected a digit, found {char:?}"))
+                })? as usize;
+                Ok(if id % 2 == 0 {
+                    Block::File(File { size, id: id / 2 })
+                } else {
+                    Block::Space(Space { size })
+                })
+            })
+            .collect::<Result<_, ParseError>>()?;
+        Ok(Disk { blocks })
+    }
+
+    /// Moves file blocks one at a time from the end of the disk into the
+    /// first free space, until no free space is left between files.
+    pub fn compact(&mut self) {
+        while self.compact_step() {}
+    }
+
+    /// Moves blocks of the last file into the first free space. Returns
+    /// whether anything moved, which is no longer the case once all free
+    /// space is behind the files.
+    fn compact_step(&mut self) -> bool {
+        let Some(first_space_index) = self
+            .blocks
+            .iter()
+            .position(|block| matches!(block, Block::Space { .. }))
+        else {
+            return false;
+        };
+        let available_space =
+            if let Some(&Block::Space(Space { size })) = self.blocks.get(first_space_index) {
+                size
+            } else {
+                0
+            };
+
+        // Find the last File block
+        let Some(last_file_index) = self
+            .blocks
+            .iter()
+            .rposition(|block| matches!(block, Block::File { .. }))
+        else {
+            // No more files to move
+            return false;
+        };
+        if last_file_index <= first_space_index {
+            // If the last file index is before or at the first space index, no valid moves are possible
+            return false;
+        }
+
+        if let Some(&Block::File(File {
+            size: file_size,
+            id,
+        })) = self.blocks.get(last_file_index)
+        {
+            if file_size <= available_space {
+                if let Some(Block::Space(Space { size })) = self.blocks.get_mut(first_space_index) {
+                    *size -= file_size; // Reduce space by file size
+                }
+                if let Some(file_block) = self.blocks.remove(last_file_index) {
+                    self.blocks.insert(first_space_index, file_block);
+                }
+
+                self.remove_empty_space(first_space_index + 1);
+            } else {
+                let remaining_size = file_size - available_space;
+
+                if let Some(Block::File(File { size, id: _id })) =
+                    self.blocks.get_mut(last_file_index)
+                {
+                    *size = remaining_size;
+                }
+
+                let new_file_block = Block::File(File {
+                    size: available_space,
+                    id,
+                });
+
+                if let Some(block) = self.blocks.get_mut(first_space_index) {
+                    *block = new_file_block;
+                }
+
+                self.remove_empty_space(first_space_index + 1);
+            }
+        }
+
+        true
+    }
+
+    fn remove_empty_space(&mut self, index: usize) {
+        if let Some(Block::Space(Space { size: 0 })) = self.blocks.get(index) {
+            self.blocks.remove(index);
+        }
+    }
+
+    /// Moves every file once, whole and in order of decreasing id, into the
+    /// first free space to its left that it fits into.
+    pub fn whole_block_reformat(&mut self) {
+        let mut moved: HashSet<usize> = HashSet::new();
+        let mut map = self.blocks.clone();
+        let mut result: VecDeque<Block> = VecDeque::new();
+
+        while let Some(block) = map.pop_back() {
+            match block {
+                Block::File(File { size: length, id }) => {
+                    if !moved.insert(id) {
+                        result.push_front(block);
+                        continue;
+                    }
+
+                    let mut found_idx = 0;
+                    let mut free_space_remaining = 0;
+                    for (i, lblock) in map.iter().enumerate() {
+                        match lblock {
+                            Block::Space(Space { size: free_size }) => {
+                                if *free_size >= length {
+                                    found_idx = i;
+                                    free_space_remaining = *free_size - length;
+                                    map.push_back(Block::Space(Space { size: length }));
+                                    break;
+                                }
+                            }
+                            Block::File(File { .. }) => {}
+                        }
+                    }
+
+                    if let Some(found_block) = map.get_mut(found_idx).filter(|_| found_idx != 0) {
+                        *found_block = block;
+                        if free_space_remaining > 0 {
+                            map.insert(
+                                found_idx + 1,
+                                Block::Space(Space {
+                                    size: free_space_remaining,
+                                }),
+                            );
+                        }
+                    } else {
+                        result.push_front(block);
+                    }
+                }
+                Block::Space(Space { .. }) => {
+                    result.push_front(block);
+                }
+            }
+        }
+
+        self.blocks = result;
+    }
+
+    /// Sum of every file block's position multiplied by its file id.
+    #[must_use]
+    pub fn compute_checksum(&self) -> usize {
+        let mut current_index = 0;
+        let mut contributions: Vec<usize> = Vec::new();
+
+        for block in &self.blocks {
+            match block {
+                Block::File(File { id, size }) => {
+                    for offset in 0..*size {
+                        let contribution = (current_index + offset) * id;
+                        contributions.push(contribution);
+                    }
+                    current_index += size;
+                }
+                Block::Space(Space { size }) => {
+                    current_index += size;
+                }
+            }
+        }
+
+        contributions.par_iter().sum()
+    }
+
+    /// Number of blocks, used and free.
+    #[must_use]
+    pub fn size(&self) -> usize {
+        self.blocks
+            .iter()
+            .map(|block| match block {
+                Block::File(File { size, .. }) | Block::Space(Space { size }) => size,
+            })
+            .sum()
+    }
+
+    /// One cell per block, showing the last digit of the file id in a color
+    /// picked by the id. Blocks past the end of the map up to `width` are
+    /// free.
+    fn canvas(&self, title: &str, width: usize) -> Canvas {
+        let mut canvas = Canvas::new(width.max(self.size()), 1, FREE_SPACE).titled(title);
+        let mut position = 0;
+        for block in &self.blocks {
+            match block {
+                Block::File(File { size, id }) => {
+                    let glyph = u32::try_from(id % 10)
+                        .ok()
+                        .and_then(|digit| char::from_digit(digit, 10))
+                        .unwrap_or('#');
+                    let cell = Cell::new(glyph, Style::fg(Color::cycle(*id)));
+                    canvas.mark((position..position + size).map(|x| Point::new(x, 0)), cell);
+                    position += size;
+                }
+                Block::Space(Space { size }) => position += size,
+            }
+        }
+        canvas
+    }
+}
+
+const FREE_SPACE: Cell = Cell::new('.', Style::PLAIN.dim());
+/// Disks are only drawn up to this many blocks, real inputs have tens of
+/// thousands.
+const MAX_DRAWN_BLOCKS: usize = 200;
+
+/// Draws the disk before compacting and after either way of compacting it.
+/// Disks too large for a terminal line are skipped.
+///
+/// # Errors
+///
+/// Returns any error from writing to `out`.
+pub fn draw_compaction(out: &mut impl Write, renderer: Renderer, disk: &Disk) -> io::Result<()> {
+    if disk.size() > MAX_DRAWN_BLOCKS {
+        return Ok(());
+    }
+
+    let mut compacted = disk.clone();
+    compacted.compact();
+    let mut reformatted = disk.clone();
+    reformatted.whole_block_reformat();
+
+    renderer.draw(out, &[&disk.canvas("Disk map", disk.size())], &[])?;
+    renderer.draw(out, &[&compacted.canvas("Moving blocks", disk.size())], &[])?;
+    renderer.draw(
+        out,
+        &[&reformatted.canvas("Moving whole files", disk.size())],
+        &[
+            (Cell::new('0', Style::fg(Color::cycle(0))), "file, by id"),
+            (FREE_SPACE, "free space"),
+        ],
+    )
+}
+
+/// Compacting the disk by moving file blocks one move at a time, starting
+/// with the disk as described by the map.
+#[derive(Debug, Clone)]
+pub struct Compaction {
+    next: Option<CompactionStep>,
+}
+
+/// The disk after a number of moves of a [`Compaction`].
+#[derive(Debug, Clone)]
+pub struct CompactionStep {
+    disk: Disk,
+    moves: usize,
+    /// Size of the disk before compacting, which the canvas keeps to.
+    width: usize,
+}
+
+impl Compaction {
+    /// The compaction of `disk` before the first block moves.
+    #[must_use]
+    pub fn new(disk: &Disk) -> Self {
+        Self {
+            next: Some(CompactionStep {
+                disk: disk.clone(),
+                moves: 0,
+                width: disk.size(),
+            }),
+        }
+    }
+}
+
+impl Iterator for Compaction {
+    type Item = CompactionStep;
+
+    fn next(&mut self) -> Option<CompactionStep> {
+        let step = self.next.take()?;
+
+        let mut next = step.clone();
+        if next.disk.compact_step() {
+            next.moves += 1;
+            self.next = Some(next);
+        }
+
+        Some(step)
+    }
+}
+
+impl Frame for CompactionStep {
+    fn canvas(&self) -> Canvas {
+        self.disk.canvas("Moving blocks", self.width)
+    }
+
+    fn counters(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("moves", self.moves.to_string()),
+            ("checksum", self.disk.compute_checksum().to_string()),
+        ]
+    }
+}
+
+/// One entry per block of the disk with the id of the file stored in it, as
+/// the reference solvers see it.
+fn expand_blocks(disk: &Disk) -> Vec<Option<usize>> {
+    disk.blocks
+        .iter()
+        .flat_map(|block| match block {
+            Block::File(File { size, id }) => vec![Some(*id); *size],
+            Block::Space(Space { size }) => vec![None; *size],
+        })
+        .collect()
+}
+
+fn block_checksum(blocks: &[Option<usize>]) -> usize {
+    blocks
+        .iter()
+        .enumerate()
+        .filter_map(|(position, id)| id.map(|id| position * id))
+        .sum()
+}
+
+/// Checksum after swapping the last file block into the first free block
+/// until no free block is left in between.
+fn reference_compact(disk: &Disk) -> usize {
+    let mut blocks = expand_blocks(disk);
+    let (mut free, mut end) = (0, blocks.len());
+    loop {
+        while free < end && blocks.get(free).is_some_and(Option::is_some) {
+            free += 1;
+        }
+        while end > free && blocks.get(end - 1).is_some_and(Option::is_none) {
+            end -= 1;
+        }
+        if free >= end {
+            break;
+        }
+        blocks.swap(free, end - 1);
+    }
+    block_checksum(&blocks)
+}
+
+/// Checksum after moving every file, highest id first, to the leftmost run
+/// of free blocks before it that is long enough, searching block by block.
+fn reference_move_files(disk: &Disk) -> usize {
+    let mut blocks = expand_blocks(disk);
+    let highest_id = blocks.iter().flatten().max().copied().unwrap_or_default();
+    for id in (0..=highest_id).rev() {
+        let Some(start) = blocks.iter().position(|&block| block == Some(id)) else {
+            continue;
+        };
+        let length = blocks
+            .iter()
+            .skip(start)
+            .take_while(|&&block| block == Some(id))
+            .count();
+        let free = (0..start)
+            .filter(|free| free + length <= start)
+            .find(|&free| {
+                blocks
+                    .get(free..free + length)
+                    .is_some_and(|run| run.iter().all(Option::is_none))
+            });
+        if let Some(free) = free {
+            for offset in 0..length {
+                blocks.swap(free + offset, start + offset);
+            }
+        }
+    }
+    block_checksum(&blocks)
+}
+
+impl Solution for DiskFragmenter {
+    const DAY: u8 = 9;
+    const TITLE: &'static str = "Disk Fragmenter";
+
+    type Input = Disk;
+    type Error = ParseError;
+
+    fn parse(input: &str) -> Result<Disk, ParseError> {
+        Disk::new(input)
+    }
+
+    /// Filesystem checksum after moving file blocks one at a time.
+    fn part_one(disk: &Disk) -> impl Display {
+        let mut disk = disk.clone();
+        disk.compact();
+        disk.compute_checksum()
+    }
+
+    /// Filesystem checksum after moving whole files.
+    fn part_two(disk: &Disk) -> impl Display {
+        let mut disk = disk.clone();
+        disk.whole_block_reformat();
+        disk.compute_checksum()
+    }
+
+    fn variants() -> Vec<Variant<Disk>> {
+        vec![
+            Variant::new("block by block", Part::One, |disk| {
+                reference_compact(disk).to_string()
+            }),
+            Variant::new("block by block", Part::Two, |disk| {
+                reference_move_files(disk).to_string()
+            }),
+        ]
+    }
+}
+
+impl Generate for DiskFragmenter {
+    const SIZE: &'static str = "digits of the disk map";
+    const DEFAULT_SIZE: usize = 19_999;
+
+    /// Files of one to nine blocks separated by up to nine free blocks,
+    /// ending with a file.
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let length = size.max(1) | 1;
+        let mut disk_map = (0..length)
+            .map(|index| {
+                let blocks = if index % 2 == 0 {
+                    rng.range(1..=9)
+                } else {
+                    rng.range(0..=9)
+                };
+                char::from(b'0' + u8::try_from(blocks).unwrap_or(0))
+            })
+            .collect::<String>();
+        disk_map.push('\n');
+        disk_map
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use std::collections::BTreeMap;
+
+    /// Disk maps of files with one to nine blocks and up to nine free blocks
+    /// between them.
+    fn disk_map() -> impl Strategy<Value = String> {
+        (prop::collection::vec((1..=9_u8, 0..=9_u8), 0..20), 1..=9_u8).prop_map(|(pairs, last)| {
+            pairs
+                .into_iter()
+                .flat_map(|(file, space)| [file, space])
+                .chain([last])
+                .map(|digit| char::from(b'0' + digit))
+                .collect()
+        })
+    }
+
+    /// Number of blocks of every file id.
+    fn file_blocks(disk: &Disk) -> BTreeMap<usize, usize> {
+        let mut blocks = BTreeMap::new();
+        for block in &disk.blocks {
+            if let Block::File(File { size, id }) = block {
+                *blocks.entry(*id).or_default() += size;
+            }
+        }
+        blocks
+    }
+
+    proptest! {
+        #[test]
+        fn test_compaction_preserves_files(disk_map in disk_map()) {
+            let disk = DiskFragmenter::parse(&disk_map).expect("a valid disk map");
+
+            let mut compacted = disk.clone();
+            compacted.compact();
+            prop_assert_eq!(file_blocks(&compacted), file_blocks(&disk));
+            prop_assert!(compacted.size() <= disk.size());
+
+            let mut reformatted = disk.clone();
+            reformatted.whole_block_reformat();
+            prop_assert_eq!(file_blocks(&reformatted), file_blocks(&disk));
+            prop_assert_eq!(reformatted.size(), disk.size());
+        }
+    }
+}
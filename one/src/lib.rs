@@ -0,0 +1,142 @@
+//! Day 1: Historian Hysteria.
+
+use aoc_common::generate::{Generate, Rng};
+use aoc_common::input::Sample;
+use aoc_common::parse::{self, ParseError};
+use aoc_common::{Part, Solution, Variant};
+use std::collections::HashMap;
+use std::fmt::Display;
+
+/// The example from the puzzle description, read from `sample.txt`.
+pub const SAMPLE: Sample = aoc_common::sample!();
+
+/// The [`Solution`] of day 1.
+pub struct HistorianHysteria;
+
+/// Both location lists, each sorted in ascending order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LocationLists {
+    first: Vec<i32>,
+    second: Vec<i32>,
+}
+
+impl Solution for HistorianHysteria {
+    const DAY: u8 = 1;
+    const TITLE: &'static str = "Historian Hysteria";
+
+    type Input = LocationLists;
+    type Error = ParseError;
+
+    fn parse(input: &str) -> Result<LocationLists, ParseError> {
+        let (mut first, mut second): (Vec<i32>, Vec<i32>) = parse::lines(input)
+            .map(|line| {
+                let [first, second] =
+                    parse::exactly(&line, line.split_whitespace(), "location IDs")?;
+                Ok((first.parse::<i32>()?, second.parse::<i32>()?))
+            })
+            .collect::<Result<Vec<_>, ParseError>>()?
+            .into_iter()
+            .unzip();
+
+        first.sort_unstable();
+        second.sort_unstable();
+
+        Ok(LocationLists { first, second })
+    }
+
+    /// Sum of the distances between the sorted lists.
+    fn part_one(lists: &LocationLists) -> impl Display {
+        lists
+            .first
+            .iter()
+            .zip(&lists.second)
+            .map(|(&first, &second)| (first - second).abs())
+            .sum::<i32>()
+    }
+
+    /// Sum of the similarity scores of the first list.
+    fn part_two(lists: &LocationLists) -> impl Display {
+        let counting_map: HashMap<i32, i32> =
+            lists
+                .second
+                .iter()
+                .fold(HashMap::new(), |mut acc, &second| {
+                    *acc.entry(second).or_insert(0) += 1;
+                    acc
+                });
+
+        lists
+            .first
+            .iter()
+            .filter_map(|&first| counting_map.get(&first).map(|&count| first * count))
+            .sum::<i32>()
+    }
+
+    fn variants() -> Vec<Variant<LocationLists>> {
+        vec![Variant::new("nested loops", Part::Two, |lists| {
+            // Every match in the second list adds the ID once more
+            lists
+                .first
+                .iter()
+                .flat_map(|&first| lists.second.iter().filter(move |&&second| second == first))
+                .sum::<i32>()
+                .to_string()
+        })]
+    }
+}
+
+impl Generate for HistorianHysteria {
+    const SIZE: &'static str = "pairs of location IDs";
+    const DEFAULT_SIZE: usize = 1000;
+
+    /// Five-digit IDs, about a third of the right list repeating IDs of the
+    /// left one so the similarity score is not zero.
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let first = (0..size)
+            .map(|_| rng.range(10_000..=99_999))
+            .collect::<Vec<_>>();
+
+        first
+            .iter()
+            .map(|&left| {
+                let right = match rng.pick(&first) {
+                    Some(&repeated) if rng.chance(30) => repeated,
+                    _ => rng.range(10_000..=99_999),
+                };
+                format!("{left}   {right}\n")
+            })
+            .collect::<Vec<_>>()
+            .concat()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn lists(pairs: &[(i32, i32)]) -> LocationLists {
+        let input = pairs
+            .iter()
+            .map(|(first, second)| format!("{first}   {second}\n"))
+            .collect::<Vec<_>>()
+            .concat();
+        HistorianHysteria::parse(&input).expect("valid location lists")
+    }
+
+    proptest! {
+        #[test]
+        fn test_distance_is_symmetric(
+            pairs in prop::collection::vec((0..100_000, 0..100_000), 0..50)
+        ) {
+            let swapped = pairs
+                .iter()
+                .map(|&(first, second)| (second, first))
+                .collect::<Vec<_>>();
+            prop_assert_eq!(
+                HistorianHysteria::part_one(&lists(&pairs)).to_string(),
+                HistorianHysteria::part_one(&lists(&swapped)).to_string()
+            );
+        }
+    }
+}
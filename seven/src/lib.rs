@@ -0,0 +1,262 @@
+//! Day 7: Bridge Repair.
+
+use aoc_common::generate::{Generate, Rng};
+use aoc_common::input::Sample;
+use aoc_common::parse::{self, ParseError, Token};
+use aoc_common::{Part, Solution, Variant};
+use rayon::prelude::*;
+use std::fmt::Display;
+
+/// The example from the puzzle description, read from `sample.txt`.
+pub const SAMPLE: Sample = aoc_common::sample!();
+
+/// The [`Solution`] of day 7.
+pub struct BridgeRepair;
+
+/// A calibration equation with its operators missing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Equation {
+    target_value: isize,
+    numbers: Vec<isize>,
+}
+
+impl Equation {
+    /// The equation of `target_value` with the operators between `numbers`
+    /// missing.
+    #[must_use]
+    pub fn new(target_value: isize, numbers: Vec<isize>) -> Self {
+        Self {
+            target_value,
+            numbers,
+        }
+    }
+
+    /// The value the numbers have to combine to.
+    #[must_use]
+    pub fn target_value(&self) -> isize {
+        self.target_value
+    }
+
+    /// The numbers in the order the operators combine them.
+    #[must_use]
+    pub fn numbers(&self) -> &[isize] {
+        &self.numbers
+    }
+}
+
+impl Solution for BridgeRepair {
+    const DAY: u8 = 7;
+    const TITLE: &'static str = "Bridge Repair";
+
+    type Input = Vec<Equation>;
+    type Error = ParseError;
+
+    fn parse(input: &str) -> Result<Vec<Equation>, ParseError> {
+        parse::lines(input).map(parse_line).collect()
+    }
+
+    /// Total calibration result of the equations solvable with `+` and `*`.
+    fn part_one(equations: &Vec<Equation>) -> impl Display {
+        equations
+            .par_iter()
+            .flat_map(evaluate_simple_expression)
+            .sum::<isize>()
+    }
+
+    /// Total calibration result of the equations solvable with `+`, `*` and
+    /// `||`.
+    fn part_two(equations: &Vec<Equation>) -> impl Display {
+        equations
+            .par_iter()
+            .flat_map(evaluate_complex_expression)
+            .sum::<isize>()
+    }
+
+    fn variants() -> Vec<Variant<Vec<Equation>>> {
+        vec![
+            Variant::new("recursive search", Part::One, |equations| {
+                reference_total(equations, false).to_string()
+            }),
+            Variant::new("recursive search", Part::Two, |equations| {
+                reference_total(equations, true).to_string()
+            }),
+        ]
+    }
+}
+
+fn parse_line(line: Token<'_>) -> Result<Equation, ParseError> {
+    let (target_value_str, numbers_str) = line.split_once(':')?;
+
+    let target_value = target_value_str.trim().parse::<isize>()?;
+    let number_list = numbers_str
+        .split_whitespace()
+        .map(|num_str| num_str.parse::<isize>())
+        .collect::<Result<Vec<_>, _>>()?;
+    if number_list.is_empty() {
+        return Err(numbers_str.error("expected at least one number"));
+    }
+
+    Ok(Equation {
+        target_value,
+        numbers: number_list,
+    })
+}
+
+/// The target value of `equation` if `+` and `*` between its numbers,
+/// evaluated left to right, can produce it. Operators that overflow an
+/// `isize` never do.
+#[must_use]
+pub fn evaluate_simple_expression(equation: &Equation) -> Option<isize> {
+    let Equation {
+        target_value,
+        numbers: number_list,
+    } = equation;
+    let (&first_number, next_numbers) = number_list.split_first()?;
+    let total_combinations = 2_isize.checked_pow(u32::try_from(next_numbers.len()).ok()?)?;
+
+    (0..total_combinations)
+        .into_par_iter()
+        .find_map_any(|combination_index| {
+            let current_result = next_numbers.iter().zip(0_u32..).try_fold(
+                first_number,
+                |current_result, (&next_number, operator_index)| {
+                    if (combination_index >> operator_index) % 2 == 0 {
+                        current_result.checked_add(next_number)
+                    } else {
+                        current_result.checked_mul(next_number)
+                    }
+                },
+            )?;
+
+            (current_result == *target_value).then_some(*target_value)
+        })
+}
+
+/// The target value of `equation` if `+`, `*` and the concatenation `||`
+/// between its numbers, evaluated left to right, can produce it. Operators
+/// that overflow an `isize` never do.
+#[must_use]
+pub fn evaluate_complex_expression(equation: &Equation) -> Option<isize> {
+    let Equation {
+        target_value,
+        numbers: number_list,
+    } = equation;
+    let (&first_number, next_numbers) = number_list.split_first()?;
+    let total_combinations = 3_usize.checked_pow(u32::try_from(next_numbers.len()).ok()?)?;
+
+    (0..total_combinations)
+        .into_par_iter()
+        .find_map_any(|combination_index| {
+            let current_result = next_numbers.iter().zip(0_u32..).try_fold(
+                first_number,
+                |current_result, (&next_number, operator_index)| {
+                    let operator = (combination_index / 3_usize.pow(operator_index)) % 3;
+
+                    Some(match operator {
+                        0 => current_result.checked_add(next_number)?,
+                        1 => current_result.checked_mul(next_number)?,
+                        _ => {
+                            let concatenated = format!("{current_result}{next_number}");
+                            concatenated.parse::<isize>().ok()?
+                        }
+                    })
+                },
+            )?;
+
+            (current_result == *target_value).then_some(*target_value)
+        })
+}
+
+/// Total calibration result found by trying the operators one number at a
+/// time, with `||` only if `concatenation` is set.
+fn reference_total(equations: &[Equation], concatenation: bool) -> isize {
+    equations
+        .iter()
+        .filter(|equation| {
+            equation
+                .numbers
+                .split_first()
+                .is_some_and(|(&first, rest)| {
+                    is_solvable(equation.target_value, first, rest, concatenation)
+                })
+        })
+        .map(|equation| equation.target_value)
+        .sum()
+}
+
+fn is_solvable(target_value: isize, value: isize, numbers: &[isize], concatenation: bool) -> bool {
+    let Some((&next_number, rest)) = numbers.split_first() else {
+        return value == target_value;
+    };
+
+    let concatenated = || format!("{value}{next_number}").parse().ok();
+    [
+        value.checked_add(next_number),
+        value.checked_mul(next_number),
+        concatenation.then(concatenated).flatten(),
+    ]
+    .into_iter()
+    .flatten()
+    .any(|value| is_solvable(target_value, value, rest, concatenation))
+}
+
+/// Digits of all numbers of a generated equation together. Any way of
+/// combining numbers with this many digits stays below 10^18, so neither the
+/// operators nor the solutions can overflow.
+const GENERATED_DIGITS: usize = 18;
+
+impl Generate for BridgeRepair {
+    const SIZE: &'static str = "equations";
+    const DEFAULT_SIZE: usize = 850;
+
+    /// Equations of two to twelve numbers. About two thirds of the test
+    /// values come from combining the numbers with random operators.
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        (0..size)
+            .map(|_| {
+                let count = rng.below(11) + 2;
+                let max_digits = (GENERATED_DIGITS / count).min(3);
+                let numbers = (0..count)
+                    .map(|_| {
+                        let digits = u32::try_from(rng.below(max_digits) + 1).unwrap_or(1);
+                        rng.range(1..=10_i64.pow(digits) - 1)
+                    })
+                    .collect::<Vec<_>>();
+
+                let (&first, rest) = numbers.split_first().unwrap_or((&0, &[]));
+                let combined = rest
+                    .iter()
+                    .fold(first, |value, &number| match rng.below(3) {
+                        0 => value + number,
+                        1 => value * number,
+                        _ => format!("{value}{number}").parse().unwrap_or(value),
+                    });
+                let target_value = if rng.chance(67) {
+                    combined
+                } else {
+                    combined + rng.range(1..=9)
+                };
+
+                let numbers = numbers.iter().map(i64::to_string).collect::<Vec<_>>();
+                format!("{target_value}: {}\n", numbers.join(" "))
+            })
+            .collect::<Vec<_>>()
+            .concat()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_overflow_is_unsolvable() {
+        let equation = Equation::new(0, vec![isize::MAX, 1, 0]);
+        assert_eq!(evaluate_simple_expression(&equation), Some(0));
+        assert_eq!(evaluate_complex_expression(&equation), Some(0));
+
+        let overflowing = Equation::new(-2, vec![isize::MAX, isize::MAX]);
+        assert_eq!(evaluate_simple_expression(&overflowing), None);
+        assert_eq!(evaluate_complex_expression(&overflowing), None);
+    }
+}
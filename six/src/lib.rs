@@ -0,0 +1,368 @@
+//! Day 6: Guard Gallivant.
+
+use aoc_common::generate::{Generate, Rng};
+use aoc_common::render::{Canvas, Cell, Color, Style};
+use aoc_common::simulation::Frame;
+use aoc_common::{input::Sample, Direction, Grid, ParseError, Part, Point, Solution, Variant};
+use rayon::prelude::*;
+use std::collections::HashSet;
+use std::fmt::Display;
+
+/// The example from the puzzle description, read from `sample.txt`.
+pub const SAMPLE: Sample = aoc_common::sample!();
+
+/// The [`Solution`] of day 6.
+pub struct GuardGallivant;
+
+/// The lab map together with the guard's starting position.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Lab {
+    grid: Grid<char>,
+    guard: Point,
+}
+
+impl Solution for GuardGallivant {
+    const DAY: u8 = 6;
+    const TITLE: &'static str = "Guard Gallivant";
+
+    type Input = Lab;
+    type Error = ParseError;
+
+    fn parse(input: &str) -> Result<Lab, ParseError> {
+        let grid = Grid::parse(input)?;
+        let guard = find_guard(&grid)?;
+
+        Ok(Lab { grid, guard })
+    }
+
+    /// Number of distinct positions the guard visits before leaving the
+    /// map.
+    fn part_one(lab: &Lab) -> impl Display {
+        find_visited_positions(&lab.grid, lab.guard).len()
+    }
+
+    /// Number of positions where a new obstacle traps the guard in a loop.
+    fn part_two(lab: &Lab) -> impl Display {
+        let visited_positions = find_visited_positions(&lab.grid, lab.guard);
+
+        find_circle_obstacle_positions(&lab.grid, lab.guard, &visited_positions).len()
+    }
+
+    fn canvases(lab: &Lab) -> Vec<Canvas> {
+        vec![path_canvas(lab)]
+    }
+
+    fn variants() -> Vec<Variant<Lab>> {
+        vec![Variant::new("full grid", Part::Two, |lab| {
+            find_circle_obstacle_positions_full_grid(&lab.grid, lab.guard)
+                .len()
+                .to_string()
+        })]
+    }
+}
+
+/// The map with the guard's path up to leaving it and her starting
+/// position.
+#[must_use]
+pub fn path_canvas(lab: &Lab) -> Canvas {
+    let mut canvas = Canvas::from_grid(&lab.grid, |_, &cell| match cell {
+        '#' => Cell::new('#', Style::fg(Color::BrightBlack).on(Color::BrightBlack)),
+        _ => Cell::new('.', Style::PLAIN.dim()),
+    })
+    .titled("Guard path");
+    canvas.mark(
+        find_visited_positions(&lab.grid, lab.guard),
+        Cell::new('X', Style::fg(Color::Yellow)),
+    );
+    canvas.set(
+        lab.guard,
+        Cell::new('^', Style::fg(Color::BrightRed).bold()),
+    );
+    canvas
+}
+
+/// The guard's patrol, one move or turn at a time, starting where she
+/// stands and ending on the last position before she leaves the map.
+#[derive(Debug, Clone)]
+pub struct Patrol<'lab> {
+    next: Option<Guard<'lab>>,
+}
+
+/// Where the guard is at one step of her [`Patrol`].
+#[derive(Debug, Clone)]
+pub struct Guard<'lab> {
+    lab: &'lab Lab,
+    position: Point,
+    direction: Direction,
+    visited: HashSet<Point>,
+    turns: usize,
+}
+
+impl<'lab> Patrol<'lab> {
+    /// The guard of `lab` before their first step.
+    #[must_use]
+    pub fn new(lab: &'lab Lab) -> Self {
+        let direction = lab
+            .grid
+            .get(lab.guard)
+            .copied()
+            .and_then(facing)
+            .unwrap_or(Direction::Up);
+
+        Self {
+            next: Some(Guard {
+                lab,
+                position: lab.guard,
+                direction,
+                visited: HashSet::from([lab.guard]),
+                turns: 0,
+            }),
+        }
+    }
+}
+
+impl<'lab> Iterator for Patrol<'lab> {
+    type Item = Guard<'lab>;
+
+    fn next(&mut self) -> Option<Guard<'lab>> {
+        let guard = self.next.take()?;
+
+        let ahead = guard
+            .position
+            .step(guard.direction)
+            .and_then(|ahead| Some((ahead, *guard.lab.grid.get(ahead)?)));
+        self.next = ahead.map(|(ahead, cell)| {
+            let mut next = guard.clone();
+            if cell == '#' {
+                next.direction = next.direction.turn_right();
+                next.turns += 1;
+            } else {
+                next.visited.insert(ahead);
+                next.position = ahead;
+            }
+            next
+        });
+
+        Some(guard)
+    }
+}
+
+impl Frame for Guard<'_> {
+    fn canvas(&self) -> Canvas {
+        let mut canvas = Canvas::from_grid(&self.lab.grid, |_, &cell| match cell {
+            '#' => Cell::new('#', Style::fg(Color::BrightBlack).on(Color::BrightBlack)),
+            _ => Cell::new('.', Style::PLAIN.dim()),
+        })
+        .titled("Guard patrol");
+        canvas.mark(
+            self.visited.iter().copied(),
+            Cell::new('X', Style::fg(Color::Yellow)),
+        );
+        let glyph = match self.direction {
+            Direction::Up => '^',
+            Direction::Right => '>',
+            Direction::Down => 'v',
+            Direction::Left => '<',
+        };
+        canvas.set(
+            self.position,
+            Cell::new(glyph, Style::fg(Color::BrightRed).bold()),
+        );
+        canvas
+    }
+
+    fn counters(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("visited", self.visited.len().to_string()),
+            ("turns", self.turns.to_string()),
+        ]
+    }
+}
+
+/// The direction a guard drawn as `glyph` is facing.
+const fn facing(glyph: char) -> Option<Direction> {
+    match glyph {
+        '^' => Some(Direction::Up),
+        '>' => Some(Direction::Right),
+        'v' => Some(Direction::Down),
+        '<' => Some(Direction::Left),
+        _ => None,
+    }
+}
+
+fn find_guard(grid: &Grid<char>) -> Result<Point, ParseError> {
+    grid.find(|cell| matches!(cell, '^' | 'v' | '<' | '>'))
+        .ok_or_else(|| ParseError::unlocated("no guard ('^', 'v', '<' or '>') on the map"))
+}
+
+fn find_visited_positions(grid: &Grid<char>, start_position: Point) -> HashSet<Point> {
+    let mut visited_positions = HashSet::new();
+    visited_positions.insert(start_position);
+
+    let mut current_position = start_position;
+    let mut current_direction = grid
+        .get(start_position)
+        .copied()
+        .and_then(facing)
+        .unwrap_or(Direction::Up);
+
+    while let Some(next_position) = current_position.step(current_direction) {
+        // Stop the loop if out of bounds
+        let Some(&cell) = grid.get(next_position) else {
+            break;
+        };
+
+        // Check for obstacle
+        if cell == '#' {
+            current_direction = current_direction.turn_right();
+            continue;
+        }
+
+        visited_positions.insert(next_position);
+        current_position = next_position;
+    }
+
+    visited_positions
+}
+
+fn find_circle_obstacle_positions(
+    grid: &Grid<char>,
+    start_position: Point,
+    visited_positions: &HashSet<Point>,
+) -> HashSet<Point> {
+    visited_positions
+        .par_iter()
+        .filter_map(|&position| {
+            if grid.get(position) == Some(&'.') {
+                // Simulate with an obstacle at position
+                let mut temp_grid = grid.clone();
+                if let Some(cell) = temp_grid.get_mut(position) {
+                    *cell = 'O';
+                }
+
+                let loop_count = simulate_guard(&temp_grid, start_position);
+                (loop_count > 0).then_some(position)
+            } else {
+                None
+            }
+        })
+        .collect::<HashSet<_>>()
+}
+
+/// Tries an obstacle on every free position of the map instead of only on
+/// the guard's path. Slower, but it doesn't rely on obstacles off the path
+/// leaving her route alone.
+fn find_circle_obstacle_positions_full_grid(
+    grid: &Grid<char>,
+    start_position: Point,
+) -> HashSet<Point> {
+    (0..grid.height())
+        .into_par_iter()
+        .flat_map(|row_index| {
+            (0..grid.width())
+                .into_par_iter()
+                .filter_map(|col_index| {
+                    let position = Point::new(col_index, row_index);
+                    if grid.get(position) == Some(&'.') {
+                        // Simulate with an obstacle at position
+                        let mut temp_grid = grid.clone();
+                        if let Some(cell) = temp_grid.get_mut(position) {
+                            *cell = 'O';
+                        }
+
+                        let loop_count = simulate_guard(&temp_grid, start_position);
+                        (loop_count > 0).then_some(position)
+                    } else {
+                        None
+                    }
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect::<HashSet<_>>()
+}
+
+fn simulate_guard(grid: &Grid<char>, start_position: Point) -> usize {
+    let mut visited_positions = HashSet::new();
+    let mut current_position = start_position;
+    let mut current_direction = match grid.get(start_position) {
+        Some('^') => Direction::Up,
+        Some('v') => Direction::Down,
+        Some('<') => Direction::Left,
+        Some('>') => Direction::Right,
+        _ => unreachable!("Invalid guard position!"),
+    };
+
+    visited_positions.insert((current_position, current_direction));
+
+    while let Some(next_position) = current_position.step(current_direction) {
+        // Stop the loop if out of bounds
+        let Some(&cell) = grid.get(next_position) else {
+            break;
+        };
+
+        // Check for obstacles
+        if cell == '#' || cell == 'O' {
+            current_direction = current_direction.turn_right();
+            continue;
+        }
+
+        // Check if the guard revisits a position with the same direction
+        if visited_positions.contains(&(next_position, current_direction)) {
+            return visited_positions.len(); // Guard is in a loop
+        }
+
+        visited_positions.insert((next_position, current_direction));
+        current_position = next_position;
+    }
+
+    0 // No loop detected
+}
+
+impl Generate for GuardGallivant {
+    const SIZE: &'static str = "grid side";
+    const DEFAULT_SIZE: usize = 130;
+
+    /// A square lab with one in twenty positions obstructed and the guard
+    /// facing up somewhere in the middle. Labs that trap the guard from the
+    /// start are rolled again, as she has to leave the map eventually.
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let size = size.max(1);
+        loop {
+            let mut grid = Grid::new(size, size, '.');
+            for _ in 0..size * size / 20 {
+                if let Some(cell) = grid.get_mut(rng.point(size, size)) {
+                    *cell = '#';
+                }
+            }
+            let guard = rng.point(size / 2, size / 2);
+            let guard = Point::new(guard.x + size / 4, guard.y + size / 4);
+            if let Some(cell) = grid.get_mut(guard) {
+                *cell = '^';
+            }
+
+            if simulate_guard(&grid, guard) == 0 {
+                return grid.to_string();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_guard_facing_right() {
+        let lab = GuardGallivant::parse(".....\n.>..#\n.....\n").expect("a valid lab");
+        let patrol_length = Patrol::new(&lab)
+            .last()
+            .map(|guard| guard.visited.len())
+            .expect("the patrol has a first step");
+
+        assert_eq!(patrol_length, 4);
+        assert_eq!(
+            GuardGallivant::part_one(&lab).to_string(),
+            patrol_length.to_string()
+        );
+    }
+}
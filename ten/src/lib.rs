@@ -0,0 +1,176 @@
+//! Day 10: Hoof It.
+
+use aoc_common::generate::{Generate, Rng};
+use aoc_common::{input::Sample, Grid, GridError, Point, Solution};
+use pathfinding::prelude::bfs_reach;
+use rayon::prelude::*;
+use std::collections::{HashSet, VecDeque};
+use std::fmt::Display;
+
+/// The example from the puzzle description, read from `sample.txt`.
+pub const SAMPLE: Sample = aoc_common::sample!();
+
+/// The [`Solution`] of day 10.
+pub struct HoofIt;
+
+/// Heights of the topographic map; impassable tiles have no height.
+pub type Map = Grid<Option<u32>>;
+
+fn trailheads(map: &Map) -> impl Iterator<Item = Point> + '_ {
+    map.iter()
+        .filter(|&(_position, &height)| height == Some(0))
+        .map(|(position, _height)| position)
+}
+
+fn height(map: &Map, position: Point) -> Option<u32> {
+    map.get(position).copied().flatten()
+}
+
+/// Neighbours of `position` that are exactly one step higher.
+fn uphill(map: &Map, position: Point) -> impl Iterator<Item = Point> + '_ {
+    let current_height = height(map, position);
+
+    map.neighbours4(position).filter(move |&next| {
+        current_height
+            .zip(height(map, next))
+            .is_some_and(|(current, new)| new == current + 1)
+    })
+}
+
+/// Sum over the trailheads of the number of height 9 positions each one
+/// reaches.
+#[must_use]
+pub fn calculate_total_score(map: &Map) -> usize {
+    trailheads(map)
+        .collect::<Vec<_>>()
+        .par_iter() // Use par_iter for parallel iteration
+        .map(|&start| reachable_nines(map, &[start]))
+        .sum()
+}
+
+fn reachable_nines(map: &Map, starts: &[Point]) -> usize {
+    let successors = |&position: &Point| uphill(map, position);
+
+    // Use Rayon to process each starting point in parallel
+    let reachable_counts: Vec<usize> = starts
+        .par_iter()
+        .map(|&start| {
+            let reachable_nodes: HashSet<Point> = bfs_reach(start, successors).collect();
+            reachable_nodes
+                .iter()
+                .filter(|&&position| height(map, position) == Some(9))
+                .count()
+        })
+        .collect();
+
+    // Sum the counts of reachable '9's from all starting points
+    reachable_counts.into_iter().sum()
+}
+
+/// Sum over the trailheads of the number of distinct hiking trails that
+/// start there.
+#[must_use]
+pub fn calculate_total_rating(map: &Map) -> usize {
+    trailheads(map)
+        .collect::<Vec<_>>()
+        .par_iter() // Use par_iter for parallel iteration over trailheads
+        .map(|&start| count_distinct_paths(map, start))
+        .sum()
+}
+
+fn count_distinct_paths(map: &Map, start: Point) -> usize {
+    let mut visited = HashSet::new();
+    let mut paths = HashSet::new();
+    let mut path = VecDeque::new();
+    path.push_back(start);
+
+    dfs(map, start, &mut visited, &mut path, &mut paths);
+
+    paths.len()
+}
+
+fn dfs(
+    map: &Map,
+    current: Point,
+    visited: &mut HashSet<Point>,
+    path: &mut VecDeque<Point>,
+    paths: &mut HashSet<VecDeque<Point>>,
+) {
+    if height(map, current) == Some(9) {
+        paths.insert(path.clone());
+        return;
+    }
+
+    visited.insert(current);
+
+    for next in uphill(map, current) {
+        if !visited.contains(&next) {
+            path.push_back(next);
+            dfs(map, next, visited, path, paths);
+            path.pop_back(); // Backtrack
+        }
+    }
+
+    visited.remove(&current);
+}
+
+impl Solution for HoofIt {
+    const DAY: u8 = 10;
+    const TITLE: &'static str = "Hoof It";
+
+    type Input = Map;
+    type Error = GridError;
+
+    fn parse(input: &str) -> Result<Map, GridError> {
+        Grid::parse_with(input, |cell| Some(cell.to_digit(10)))
+    }
+
+    /// Sum of the scores of all trailheads.
+    fn part_one(map: &Map) -> impl Display {
+        calculate_total_score(map)
+    }
+
+    /// Sum of the ratings of all trailheads.
+    fn part_two(map: &Map) -> impl Display {
+        calculate_total_rating(map)
+    }
+}
+
+impl Generate for HoofIt {
+    const SIZE: &'static str = "grid side";
+    const DEFAULT_SIZE: usize = 55;
+
+    /// Random heights with hiking trails from 0 to 9 walked into them, one
+    /// trail for every thirty positions. Trails never cross each other, so
+    /// a trail stops early where it runs into others.
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let mut grid = Grid::new(size, size, 0);
+        for position in grid.positions().collect::<Vec<_>>() {
+            if let Some(height) = grid.get_mut(position) {
+                *height = rng.below(10);
+            }
+        }
+
+        let mut walked = Grid::new(size, size, false);
+        for _ in 0..size * size / 30 {
+            let mut position = Some(rng.point(size, size));
+            for height in 0..10 {
+                let Some(current) = position.filter(|&current| walked.get(current) == Some(&false))
+                else {
+                    break;
+                };
+                if let (Some(cell), Some(taken)) = (grid.get_mut(current), walked.get_mut(current))
+                {
+                    *cell = height;
+                    *taken = true;
+                }
+                let free = walked
+                    .neighbours4(current)
+                    .filter(|&neighbour| walked.get(neighbour) == Some(&false))
+                    .collect::<Vec<_>>();
+                position = rng.pick(&free).copied();
+            }
+        }
+        grid.to_string()
+    }
+}
@@ -0,0 +1,177 @@
+//! Day 13: Claw Contraption.
+
+use aoc_common::generate::{Generate, Rng};
+use aoc_common::input::Sample;
+use aoc_common::parse::{self, ParseError, Token};
+use aoc_common::Solution;
+use std::fmt::Display;
+
+/// The example from the puzzle description, read from `sample.txt`.
+pub const SAMPLE: Sample = aoc_common::sample!();
+
+/// The [`Solution`] of day 13.
+pub struct ClawContraption;
+
+/// How far one press of a button moves the claw.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Button {
+    /// Distance along the X axis.
+    pub x: i64,
+    /// Distance along the Y axis.
+    pub y: i64,
+}
+
+/// Where the prize is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Price {
+    /// Position along the X axis.
+    pub x: i64,
+    /// Position along the Y axis.
+    pub y: i64,
+}
+
+/// A claw machine with its two buttons and the prize location.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClawMachine {
+    /// Costs three tokens per press.
+    pub button_a: Button,
+    /// Costs one token per press.
+    pub button_b: Button,
+    /// Where the claw has to end up to win the prize.
+    pub price: Price,
+}
+
+/// Parses the `X` and `Y` components of a line like `X+94, Y+34`, where
+/// `sign` is the character between each axis and its value.
+fn parse_coordinates(token: Token<'_>, sign: char) -> Result<(i64, i64), ParseError> {
+    let (x, y) = token.split_once(',')?;
+    let x = x.trim().strip_prefix(&format!("X{sign}"))?.parse()?;
+    let y = y.trim().strip_prefix(&format!("Y{sign}"))?.parse()?;
+    Ok((x, y))
+}
+
+fn parse_button(line: Token<'_>, name: char) -> Result<Button, ParseError> {
+    let offsets = line.strip_prefix(&format!("Button {name}:"))?;
+    let (x, y) = parse_coordinates(offsets, '+')?;
+    Ok(Button { x, y })
+}
+
+fn parse_price(line: Token<'_>) -> Result<Price, ParseError> {
+    let location = line.strip_prefix("Prize:")?;
+    let (x, y) = parse_coordinates(location, '=')?;
+    Ok(Price { x, y })
+}
+
+/// Fewest tokens that move the claw onto `price`, or `None` if no whole
+/// numbers of presses reach it. Unless `part_one`, the prize is first moved
+/// by 10 000 000 000 000 along both axes.
+#[expect(
+    clippy::cast_precision_loss,
+    clippy::cast_possible_truncation,
+    reason = "the coordinates stay far below the range where f64 loses integer precision"
+)]
+#[must_use]
+pub fn calculate_min_tokens(
+    button_a: &Button,
+    button_b: &Button,
+    mut price: Price,
+    part_one: bool,
+) -> Option<i64> {
+    let denominator = button_b.y * button_a.x - button_b.x * button_a.y;
+    if denominator == 0 {
+        return None;
+    }
+
+    if !part_one {
+        price.x += 10_000_000_000_000;
+        price.y += 10_000_000_000_000;
+    }
+
+    let times_b = (price.y * button_a.x - price.x * button_a.y) as f64 / denominator as f64;
+    let times_a = (price.x - button_b.x * times_b.round() as i64) as f64 / button_a.x as f64;
+
+    if times_a >= 0.0 && times_b >= 0.0 && times_a.fract() == 0.0 && times_b.fract() == 0.0 {
+        let total_tokens = (times_a as i64) * 3 + (times_b.round() as i64);
+        return Some(total_tokens);
+    }
+    None
+}
+
+/// Fewest tokens needed to win every prize of `machines` that can be won.
+#[must_use]
+pub fn total_tokens(machines: &[ClawMachine], part_one: bool) -> i64 {
+    machines
+        .iter()
+        .filter_map(|machine| {
+            calculate_min_tokens(
+                &machine.button_a,
+                &machine.button_b,
+                machine.price,
+                part_one,
+            )
+        })
+        .sum()
+}
+
+impl Solution for ClawContraption {
+    const DAY: u8 = 13;
+    const TITLE: &'static str = "Claw Contraption";
+
+    type Input = Vec<ClawMachine>;
+    type Error = ParseError;
+
+    fn parse(input: &str) -> Result<Vec<ClawMachine>, ParseError> {
+        parse::paragraphs(input)
+            .map(|machine| {
+                let [button_a, button_b, price] = parse::paragraph_lines(machine)?;
+                Ok(ClawMachine {
+                    button_a: parse_button(button_a, 'A')?,
+                    button_b: parse_button(button_b, 'B')?,
+                    price: parse_price(price)?,
+                })
+            })
+            .collect()
+    }
+
+    /// Fewest tokens needed to win every winnable prize.
+    fn part_one(machines: &Vec<ClawMachine>) -> impl Display {
+        total_tokens(machines, true)
+    }
+
+    /// Fewest tokens needed to win every winnable prize after correcting the
+    /// prize positions by 10 000 000 000 000.
+    fn part_two(machines: &Vec<ClawMachine>) -> impl Display {
+        total_tokens(machines, false)
+    }
+}
+
+impl Generate for ClawContraption {
+    const SIZE: &'static str = "claw machines";
+    const DEFAULT_SIZE: usize = 320;
+
+    /// Machines with buttons moving the claw 10 to 99 along each axis.
+    /// About half of the prizes are reachable within a hundred presses of
+    /// each button.
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let machines = (0..size)
+            .map(|_| {
+                let button_a = (rng.range(10..=99), rng.range(10..=99));
+                let button_b = (rng.range(10..=99), rng.range(10..=99));
+                let prize = if rng.chance(50) {
+                    let (times_a, times_b) = (rng.range(1..=100), rng.range(1..=100));
+                    (
+                        times_a * button_a.0 + times_b * button_b.0,
+                        times_a * button_a.1 + times_b * button_b.1,
+                    )
+                } else {
+                    (rng.range(1000..=20_000), rng.range(1000..=20_000))
+                };
+                format!(
+                    "Button A: X+{}, Y+{}\nButton B: X+{}, Y+{}\nPrize: X={}, Y={}\n",
+                    button_a.0, button_a.1, button_b.0, button_b.1, prize.0, prize.1
+                )
+            })
+            .collect::<Vec<_>>();
+        machines.join("\n")
+    }
+}
@@ -0,0 +1,182 @@
+//! Day 3: Mull It Over.
+
+use aoc_common::generate::{Generate, Rng};
+use aoc_common::input::Sample;
+use aoc_common::Solution;
+use std::convert::Infallible;
+use std::fmt::Display;
+use winnow::{
+    ascii::digit1,
+    error::{ErrMode, InputError},
+    token::{literal, one_of},
+    ModalResult, Parser,
+};
+
+/// The example from the puzzle description, read from `sample.txt`.
+pub const SAMPLE: Sample = aoc_common::sample!();
+
+/// The [`Solution`] of day 3.
+pub struct MullItOver;
+
+/// An instruction found in the corrupted memory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Instruction {
+    /// `mul(X,Y)`, adding the product of the numbers to the result unless
+    /// it overflows an `isize`.
+    Mul(isize, isize),
+    /// `do()`, enabling the multiplications after it.
+    Do,
+    /// `don't()`, disabling the multiplications after it.
+    Dont,
+}
+
+/// The remaining input together with the parsed value.
+type PeekResult<'str, O> = Result<(&'str str, O), ErrMode<InputError<&'str str>>>;
+
+fn parse_number<'str>(input: &mut &'str str) -> ModalResult<isize, InputError<&'str str>> {
+    digit1.try_map(str::parse).parse_next(input)
+}
+
+fn parse_mul(input: &str) -> PeekResult<'_, (isize, isize)> {
+    let (input, _) = literal("mul").parse_peek(input)?;
+    let (input, _) = one_of('(').parse_peek(input)?;
+    let (input, x) = parse_number.parse_peek(input)?;
+    let (input, _) = one_of(',').parse_peek(input)?;
+    let (input, y) = parse_number.parse_peek(input)?;
+    let (input, _) = one_of(')').parse_peek(input)?;
+    Ok((input, (x, y)))
+}
+
+fn parse_do(input: &str) -> PeekResult<'_, bool> {
+    let (input, _) = literal("do()").parse_peek(input)?;
+    Ok((input, true))
+}
+
+fn parse_dont(input: &str) -> PeekResult<'_, bool> {
+    let (input, _) = literal("don't()").parse_peek(input)?;
+    Ok((input, false))
+}
+
+impl Solution for MullItOver {
+    const DAY: u8 = 3;
+    const TITLE: &'static str = "Mull It Over";
+
+    type Input = Vec<Instruction>;
+    type Error = Infallible;
+
+    fn parse(input: &str) -> Result<Vec<Instruction>, Infallible> {
+        let mut instructions = Vec::new();
+        let mut remaining_input = input;
+
+        while !remaining_input.is_empty() {
+            if let Ok((next_input, enabled)) =
+                parse_do(remaining_input).or_else(|_| parse_dont(remaining_input))
+            {
+                instructions.push(if enabled {
+                    Instruction::Do
+                } else {
+                    Instruction::Dont
+                });
+                remaining_input = next_input;
+                continue;
+            }
+
+            if let Ok((next_input, (x, y))) = parse_mul(remaining_input) {
+                instructions.push(Instruction::Mul(x, y));
+                remaining_input = next_input;
+            } else {
+                // If parsing fails, consume one character and continue
+                let mut chars = remaining_input.chars();
+                chars.next();
+                remaining_input = chars.as_str();
+            }
+        }
+
+        Ok(instructions)
+    }
+
+    /// Sum of the products of all `mul` instructions.
+    fn part_one(instructions: &Vec<Instruction>) -> impl Display {
+        instructions
+            .iter()
+            .map(|instruction| match instruction {
+                Instruction::Mul(x, y) => x.checked_mul(*y).unwrap_or_default(),
+                Instruction::Do | Instruction::Dont => 0,
+            })
+            .sum::<isize>()
+    }
+
+    /// Sum of the products of the `mul` instructions enabled by
+    /// `do()`/`don't()`.
+    fn part_two(instructions: &Vec<Instruction>) -> impl Display {
+        let mut enabled = true;
+        let mut total = 0;
+
+        for instruction in instructions {
+            match instruction {
+                Instruction::Mul(x, y) if enabled => {
+                    total += x.checked_mul(*y).unwrap_or_default();
+                }
+                Instruction::Mul(..) => {}
+                Instruction::Do => enabled = true,
+                Instruction::Dont => enabled = false,
+            }
+        }
+
+        total
+    }
+}
+
+/// Near misses of instructions that the parser has to skip.
+const CORRUPTED: &[&str] = &[
+    "mul[3,7]",
+    "mul(32,64]",
+    "mul ( 2 , 4 )",
+    "mul(4*",
+    "mul(6,9!",
+    "?(12,34)",
+    "do_not_",
+    "don't",
+    "do(",
+];
+const NOISE: &[u8] = b"!@#$%^&*()[]{}<>,;:'?+-_ whathowwhenwhereselectfromwhy\n";
+
+impl Generate for MullItOver {
+    const SIZE: &'static str = "instructions";
+    const DEFAULT_SIZE: usize = 800;
+
+    /// Multiplications with up to three digits per number and switches,
+    /// among corrupted instructions and noise.
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let mut memory = String::new();
+        for _ in 0..size {
+            for _ in 0..rng.below(12) {
+                memory.push(rng.pick(NOISE).map_or(' ', |&byte| char::from(byte)));
+            }
+            if rng.chance(20) {
+                memory.push_str(rng.pick(CORRUPTED).unwrap_or(&""));
+            }
+            let instruction = match rng.below(10) {
+                0 => "do()".to_owned(),
+                1 => "don't()".to_owned(),
+                _ => format!("mul({},{})", rng.range(1..=999), rng.range(1..=999)),
+            };
+            memory.push_str(&instruction);
+        }
+        memory.push('\n');
+        memory
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_overflowing_product_is_skipped() {
+        let instructions = MullItOver::parse("mul(9999999999,9999999999)mul(2,3)")
+            .expect("the parser skips what it cannot read");
+        assert_eq!(MullItOver::part_one(&instructions).to_string(), "6");
+        assert_eq!(MullItOver::part_two(&instructions).to_string(), "6");
+    }
+}
@@ -0,0 +1,396 @@
+//! Day 12: Garden Groups.
+
+use aoc_common::generate::{Generate, Rng};
+use aoc_common::render::{Canvas, Cell, Color, Style};
+use aoc_common::{input::Sample, Direction, Grid, GridError, Part, Point, Solution, Variant};
+use std::collections::{HashMap, HashSet};
+use std::fmt::Display;
+use std::hash::BuildHasher;
+
+/// The example from the puzzle description, read from `sample.txt`.
+pub const SAMPLE: Sample = aoc_common::sample!();
+
+/// The [`Solution`] of day 12.
+pub struct GardenGroups;
+
+/// The regions of a single plant type.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FarmPlot {
+    regions: Vec<HashSet<(usize, usize)>>,
+}
+
+impl FarmPlot {
+    /// A plant type without any plots yet.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the plot at `row_index` and `column_index` to the region of the
+    /// plot above or to its left, merging the two regions if both belong to
+    /// this plant type. Plots have to be added row by row, left to right,
+    /// for every region to be found.
+    pub fn add_position(&mut self, row_index: usize, column_index: usize) {
+        let mut top_region_index = None;
+        let mut left_region_index = None;
+
+        for (region_index, region) in self.regions.iter().enumerate() {
+            if top_region_index.is_none()
+                && row_index > 0
+                && region.contains(&(row_index - 1, column_index))
+            {
+                top_region_index = Some(region_index);
+            }
+            if left_region_index.is_none()
+                && column_index > 0
+                && region.contains(&(row_index, column_index - 1))
+            {
+                left_region_index = Some(region_index);
+            }
+
+            if top_region_index.is_some() && left_region_index.is_some() {
+                break;
+            }
+        }
+
+        match (top_region_index, left_region_index) {
+            (Some(i), Some(j)) if i != j => {
+                let (smaller_index, larger_index) = if j < i { (j, i) } else { (i, j) };
+                let merged_region = self.regions.remove(larger_index);
+                if let Some(region) = self.regions.get_mut(smaller_index) {
+                    region.insert((row_index, column_index));
+                    region.extend(merged_region);
+                }
+            }
+            (Some(i), _) | (None, Some(i)) => {
+                if let Some(region) = self.regions.get_mut(i) {
+                    region.insert((row_index, column_index));
+                }
+            }
+            (None, None) => {
+                self.regions
+                    .push(HashSet::from([(row_index, column_index)]));
+            }
+        }
+    }
+
+    /// The regions, each as the `(row, column)` positions of its plots.
+    #[must_use]
+    pub fn regions(&self) -> &[HashSet<(usize, usize)>] {
+        &self.regions
+    }
+
+    /// Fencing price of every region as its area times its number of sides,
+    /// counted as its number of corners.
+    #[must_use]
+    pub fn calculate_corner_costs(&self) -> usize {
+        let mut total_cost = 0;
+
+        for region in &self.regions {
+            let area = region.len();
+            let corner_count = Self::get_corner_count(region);
+            total_cost += area * corner_count;
+        }
+
+        total_cost
+    }
+
+    /// Fencing price of every region as its area times its perimeter.
+    #[must_use]
+    pub fn calculate_perimeter_costs(&self) -> usize {
+        let mut total_cost = 0;
+
+        for region in &self.regions {
+            let area = region.len();
+            let perimeter = Self::get_perimeter(region);
+            total_cost += area * perimeter;
+        }
+
+        total_cost
+    }
+
+    fn get_corner_count(region: &HashSet<(usize, usize)>) -> usize {
+        let mut corner_count = 0;
+
+        for &(row_index, column_index) in region {
+            let is_top = region.contains(&(row_index.wrapping_sub(1), column_index));
+            let is_bottom = region.contains(&(row_index + 1, column_index));
+            let is_left = region.contains(&(row_index, column_index.wrapping_sub(1)));
+            let is_right = region.contains(&(row_index, column_index + 1));
+
+            let is_top_left =
+                region.contains(&(row_index.wrapping_sub(1), column_index.wrapping_sub(1)));
+            let is_top_right = region.contains(&(row_index.wrapping_sub(1), column_index + 1));
+            let is_bottom_left = region.contains(&(row_index + 1, column_index.wrapping_sub(1)));
+            let is_bottom_right = region.contains(&(row_index + 1, column_index + 1));
+
+            if !is_top && !is_right || is_top && is_right && !is_top_right {
+                corner_count += 1;
+            }
+            if !is_top && !is_left || is_top && is_left && !is_top_left {
+                corner_count += 1;
+            }
+            if !is_bottom && !is_right || is_bottom && is_right && !is_bottom_right {
+                corner_count += 1;
+            }
+            if !is_bottom && !is_left || is_bottom && is_left && !is_bottom_left {
+                corner_count += 1;
+            }
+        }
+
+        corner_count
+    }
+
+    fn get_perimeter(region: &HashSet<(usize, usize)>) -> usize {
+        let mut perimeter = 0;
+
+        for &(row_index, column_index) in region {
+            let is_top = region.contains(&(row_index.wrapping_sub(1), column_index));
+            let is_bottom = region.contains(&(row_index + 1, column_index));
+            let is_left = region.contains(&(row_index, column_index.wrapping_sub(1)));
+            let is_right = region.contains(&(row_index, column_index + 1));
+
+            if !is_top {
+                perimeter += 1;
+            }
+            if !is_bottom {
+                perimeter += 1;
+            }
+            if !is_left {
+                perimeter += 1;
+            }
+            if !is_right {
+                perimeter += 1;
+            }
+        }
+
+        perimeter
+    }
+}
+
+/// The garden with every region in its own color, as far as the palette
+/// goes.
+#[must_use]
+pub fn region_canvas<S: BuildHasher>(plots: &HashMap<char, FarmPlot, S>) -> Canvas {
+    let positions = || plots.values().flat_map(|plot| &plot.regions).flatten();
+    let height = positions()
+        .map(|&(row, _)| row + 1)
+        .max()
+        .unwrap_or_default();
+    let width = positions()
+        .map(|&(_, column)| column + 1)
+        .max()
+        .unwrap_or_default();
+    let mut canvas = Canvas::new(width, height, Cell::plain(' ')).titled("Regions");
+
+    // Sort the plant types so the colors do not change between runs
+    let mut plants = plots.iter().collect::<Vec<_>>();
+    plants.sort_unstable_by_key(|&(&plant, _)| plant);
+    let regions = plants
+        .into_iter()
+        .flat_map(|(&plant, plot)| plot.regions.iter().map(move |region| (plant, region)));
+
+    for (index, (plant, region)) in regions.enumerate() {
+        let cell = Cell::new(plant, Style::fg(Color::cycle(index)));
+        canvas.mark(
+            region.iter().map(|&(row, column)| Point::new(column, row)),
+            cell,
+        );
+    }
+    canvas
+}
+
+/// Total fencing price of the regions found by flooding the garden from
+/// every plot not yet in a region. With `sides`, a straight fence counts once
+/// instead of once per plot along it.
+fn reference_price<S: BuildHasher>(plots: &HashMap<char, FarmPlot, S>, sides: bool) -> usize {
+    let garden = plots
+        .iter()
+        .flat_map(|(&plant, plot)| {
+            plot.regions
+                .iter()
+                .flatten()
+                .map(move |&(row, column)| (Point::new(column, row), plant))
+        })
+        .collect::<HashMap<_, _>>();
+
+    let mut flooded = HashSet::new();
+    let mut total_price = 0;
+    for (&start, &plant) in &garden {
+        if !flooded.insert(start) {
+            continue;
+        }
+
+        let mut region = vec![start];
+        let mut frontier = vec![start];
+        while let Some(position) = frontier.pop() {
+            for direction in Direction::ALL {
+                let Some(neighbour) = position.step(direction) else {
+                    continue;
+                };
+                if garden.get(&neighbour) == Some(&plant) && flooded.insert(neighbour) {
+                    region.push(neighbour);
+                    frontier.push(neighbour);
+                }
+            }
+        }
+
+        // Plots next to the region with the same plant belong to it
+        let is_inside = |position: Option<Point>| {
+            position.is_some_and(|position| garden.get(&position) == Some(&plant))
+        };
+        let fences = region.iter().flat_map(|&position| {
+            Direction::ALL
+                .into_iter()
+                .filter(move |&direction| !is_inside(position.step(direction)))
+                .map(move |direction| (position, direction))
+        });
+        // Looking out over a fence, the side goes on in the plot to the right
+        // if that one is fenced the same way, so only its last plot counts
+        let fence_count = if sides {
+            fences
+                .filter(|&(position, direction)| {
+                    let next = position.step(direction.turn_right());
+                    !is_inside(next) || is_inside(next.and_then(|next| next.step(direction)))
+                })
+                .count()
+        } else {
+            fences.count()
+        };
+        total_price += region.len() * fence_count;
+    }
+    total_price
+}
+
+impl Solution for GardenGroups {
+    const DAY: u8 = 12;
+    const TITLE: &'static str = "Garden Groups";
+
+    type Input = HashMap<char, FarmPlot>;
+    type Error = GridError;
+
+    fn parse(input: &str) -> Result<HashMap<char, FarmPlot>, GridError> {
+        let grid = Grid::<char>::parse(input)?;
+
+        let mut plots = HashMap::new();
+
+        for (position, area) in grid.iter() {
+            plots
+                .entry(*area)
+                .or_insert_with(FarmPlot::new)
+                .add_position(position.y, position.x);
+        }
+
+        Ok(plots)
+    }
+
+    /// Total fencing price based on the perimeter of every region.
+    fn part_one(plots: &HashMap<char, FarmPlot>) -> impl Display {
+        plots
+            .values()
+            .map(FarmPlot::calculate_perimeter_costs)
+            .sum::<usize>()
+    }
+
+    /// Total fencing price based on the number of sides of every region.
+    fn part_two(plots: &HashMap<char, FarmPlot>) -> impl Display {
+        plots
+            .values()
+            .map(FarmPlot::calculate_corner_costs)
+            .sum::<usize>()
+    }
+
+    fn canvases(plots: &HashMap<char, FarmPlot>) -> Vec<Canvas> {
+        vec![region_canvas(plots)]
+    }
+
+    fn variants() -> Vec<Variant<HashMap<char, FarmPlot>>> {
+        vec![
+            Variant::new("flood fill", Part::One, |plots| {
+                reference_price(plots, false).to_string()
+            }),
+            Variant::new("flood fill", Part::Two, |plots| {
+                reference_price(plots, true).to_string()
+            }),
+        ]
+    }
+}
+
+impl Generate for GardenGroups {
+    const SIZE: &'static str = "grid side";
+    const DEFAULT_SIZE: usize = 140;
+
+    /// Regions grown from one seed for every sixty plots, each planting one
+    /// of the 26 letters, growing into free neighbours in random order.
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let mut garden = Grid::new(size, size, None);
+        let mut frontier = Vec::new();
+        for _ in 0..(size * size / 60).max(1) {
+            let seed = rng.point(size, size);
+            let plant = char::from(b'A' + u8::try_from(rng.below(26)).unwrap_or(0));
+            if let Some(plot) = garden.get_mut(seed) {
+                *plot = Some(plant);
+                frontier.push(seed);
+            }
+        }
+
+        while !frontier.is_empty() {
+            let position = frontier.swap_remove(rng.below(frontier.len()));
+            let plant = garden.get(position).copied().flatten();
+            for neighbour in garden.neighbours4(position).collect::<Vec<_>>() {
+                if let Some(plot @ None) = garden.get_mut(neighbour) {
+                    *plot = plant;
+                    frontier.push(neighbour);
+                }
+            }
+        }
+
+        garden.map(|plot| plot.unwrap_or('A')).to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    /// Small gardens with few plant types, so regions get irregular shapes.
+    fn garden() -> impl Strategy<Value = String> {
+        (1..10_usize, 1..10_usize)
+            .prop_flat_map(|(width, height)| {
+                let plant = prop::sample::select(vec!['A', 'B', 'C']);
+                prop::collection::vec(prop::collection::vec(plant, width), height)
+            })
+            .prop_map(|rows| {
+                rows.into_iter()
+                    .map(|row| format!("{}\n", String::from_iter(row)))
+                    .collect::<Vec<_>>()
+                    .concat()
+            })
+    }
+
+    proptest! {
+        #[test]
+        fn test_perimeter_is_at_least_sides(garden in garden()) {
+            let plots = GardenGroups::parse(&garden).expect("a rectangular garden");
+            for region in plots.values().flat_map(|plot| &plot.regions) {
+                let sides = FarmPlot::get_corner_count(region);
+                prop_assert!(sides >= 4);
+                prop_assert!(FarmPlot::get_perimeter(region) >= sides);
+            }
+        }
+    }
+
+    #[test]
+    fn test_farm_plot() {
+        // An L of three plots and a single plot apart from it
+        let mut plot = FarmPlot::new();
+        for (row, column) in [(0, 0), (0, 1), (0, 3), (1, 0)] {
+            plot.add_position(row, column);
+        }
+
+        assert_eq!(plot.regions().len(), 2);
+        assert_eq!(plot.calculate_perimeter_costs(), 3 * 8 + 4);
+        assert_eq!(plot.calculate_corner_costs(), 3 * 6 + 4);
+    }
+}
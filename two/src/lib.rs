@@ -0,0 +1,136 @@
+//! Day 2: Red-Nosed Reports.
+
+use aoc_common::generate::{Generate, Rng};
+use aoc_common::input::Sample;
+use aoc_common::parse::{self, ParseError};
+use aoc_common::Solution;
+use std::fmt::Display;
+
+/// The example from the puzzle description, read from `sample.txt`.
+pub const SAMPLE: Sample = aoc_common::sample!();
+
+/// The [`Solution`] of day 2.
+pub struct RedNosedReports;
+
+/// The rules a report of levels has to follow to be safe.
+pub trait SafetyCriteria {
+    /// Whether no two neighbouring levels differ by more than three.
+    fn differ_at_most_three(&self) -> bool;
+    /// Whether any two neighbouring levels differ at all.
+    fn differ_at_least_one(&self) -> bool;
+    /// Whether every level is higher than the one before it.
+    fn is_increasing(&self) -> bool;
+    /// Whether every level is lower than the one before it.
+    fn is_decreasing(&self) -> bool;
+    /// Whether the report is safe: all increasing or all decreasing, by one
+    /// to three at a time.
+    fn meets_differ_conditions(&self) -> bool;
+    /// Whether the report is safe once a single level is removed.
+    fn can_become_safe_by_removing_one(&self) -> bool;
+}
+
+/// Every level paired with the one after it.
+fn neighbours(levels: &[i32]) -> impl Iterator<Item = (i32, i32)> + '_ {
+    levels.iter().copied().zip(levels.iter().copied().skip(1))
+}
+
+impl SafetyCriteria for [i32] {
+    fn is_increasing(&self) -> bool {
+        neighbours(self).all(|(first, second)| first < second)
+    }
+
+    fn is_decreasing(&self) -> bool {
+        neighbours(self).all(|(first, second)| first > second)
+    }
+
+    fn differ_at_least_one(&self) -> bool {
+        neighbours(self).any(|(first, second)| first.abs_diff(second) > 0)
+    }
+
+    fn differ_at_most_three(&self) -> bool {
+        neighbours(self).all(|(first, second)| first.abs_diff(second) < 4)
+    }
+
+    fn meets_differ_conditions(&self) -> bool {
+        (self.is_increasing() || self.is_decreasing())
+            && self.differ_at_least_one()
+            && self.differ_at_most_three()
+    }
+
+    fn can_become_safe_by_removing_one(&self) -> bool {
+        (0..self.len()).any(|i| {
+            let mut modified_report = self.to_vec();
+            modified_report.remove(i);
+            modified_report.meets_differ_conditions()
+        })
+    }
+}
+
+impl Solution for RedNosedReports {
+    const DAY: u8 = 2;
+    const TITLE: &'static str = "Red-Nosed Reports";
+
+    type Input = Vec<Vec<i32>>;
+    type Error = ParseError;
+
+    fn parse(input: &str) -> Result<Vec<Vec<i32>>, ParseError> {
+        parse::lines(input)
+            .map(|line| {
+                line.split_whitespace()
+                    .map(|level| level.parse::<i32>())
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .collect()
+    }
+
+    /// Number of reports that are safe as they are.
+    fn part_one(reports: &Vec<Vec<i32>>) -> impl Display {
+        reports
+            .iter()
+            .filter(|report| report.meets_differ_conditions())
+            .count()
+    }
+
+    /// Number of reports that are safe after removing at most one level.
+    fn part_two(reports: &Vec<Vec<i32>>) -> impl Display {
+        reports
+            .iter()
+            .filter(|report| report.can_become_safe_by_removing_one())
+            .count()
+    }
+}
+
+impl Generate for RedNosedReports {
+    const SIZE: &'static str = "reports";
+    const DEFAULT_SIZE: usize = 1000;
+
+    /// Reports of five to eight levels that steadily rise or fall, a third
+    /// of them with one level that breaks the rules.
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        (0..size)
+            .map(|_| {
+                let increasing = rng.chance(50);
+                let mut level = if increasing {
+                    rng.range(1..=50)
+                } else {
+                    rng.range(50..=99)
+                };
+                let length = rng.range(5..=8);
+                let broken = rng.chance(33).then(|| rng.range(1..=length - 1));
+
+                let mut levels = vec![level.to_string()];
+                for index in 1..length {
+                    let step = if broken == Some(index) {
+                        rng.range(-2..=6)
+                    } else {
+                        rng.range(1..=3)
+                    };
+                    level += if increasing { step } else { -step };
+                    levels.push(level.to_string());
+                }
+                format!("{}\n", levels.join(" "))
+            })
+            .collect::<Vec<_>>()
+            .concat()
+    }
+}